@@ -0,0 +1,107 @@
+//! Benchmark comparing `Signals::receive_info` and `Signals::receive_many`.
+//!
+//! Run using `cargo bench --bench receive`.
+//!
+//! Every call to either method is a single system call (`read(2)` or
+//! `kevent(2)`), so next to the time taken we also report the number of calls
+//! needed to receive all signals.
+//!
+//! On Android and Linux we use real-time signals, which are queued. Other
+//! signals are merged, so on other platforms every round sends a number of
+//! different signals instead.
+
+use std::hint::black_box;
+use std::process;
+use std::time::{Duration, Instant};
+
+use mio_signals::{Signal, SignalInfo, SignalSet, Signals, send_signal};
+
+/// Number of rounds to run each benchmark.
+const ROUNDS: usize = 1000;
+/// Size of the buffer used in `receive_many`.
+const BUF_SIZE: usize = 32;
+
+fn main() {
+    let (set, burst) = burst();
+    let mut signals = Signals::new(set).expect("failed to create Signals");
+
+    let mut calls = 0;
+    let single = run(&mut signals, &burst, |signals| {
+        let mut received = 0;
+        loop {
+            calls += 1;
+            match signals.receive_info().unwrap() {
+                Some(info) => {
+                    let _ = black_box(info);
+                    received += 1;
+                }
+                None => return received,
+            }
+        }
+    });
+    report("receive_info", burst.len(), single, calls);
+
+    let mut calls = 0;
+    let mut infos = [SignalInfo::default(); BUF_SIZE];
+    let many = run(&mut signals, &burst, |signals| {
+        let mut received = 0;
+        loop {
+            calls += 1;
+            let n = signals.receive_many(&mut infos).unwrap();
+            let _ = black_box(&infos[..n]);
+            received += n;
+            if n < infos.len() {
+                return received;
+            }
+        }
+    });
+    report("receive_many", burst.len(), many, calls);
+}
+
+/// Returns the signals to listen for and the signals to send each round.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn burst() -> (SignalSet, Vec<Signal>) {
+    let set = Signal::Realtime(1) | Signal::Realtime(2);
+    let burst = (0..128).map(|n| Signal::Realtime(1 + (n % 2))).collect();
+    (set, burst)
+}
+
+/// Returns the signals to listen for and the signals to send each round.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn burst() -> (SignalSet, Vec<Signal>) {
+    let set = SignalSet::all() | Signal::WindowChange;
+    (set, set.into_iter().collect())
+}
+
+/// Run `ROUNDS` rounds of sending `burst` and receiving it using `receive`,
+/// returning the total time spent receiving.
+fn run<F>(signals: &mut Signals, burst: &[Signal], mut receive: F) -> Duration
+where
+    F: FnMut(&mut Signals) -> usize,
+{
+    let pid = process::id();
+    let mut total = Duration::ZERO;
+    for _ in 0..ROUNDS {
+        for signal in burst {
+            send_signal(pid, *signal).expect("failed to send signal");
+        }
+        let start = Instant::now();
+        let received = receive(signals);
+        total += start.elapsed();
+        assert_eq!(received, burst.len(), "didn't receive all signals");
+    }
+    total
+}
+
+fn report(name: &str, burst: usize, elapsed: Duration, calls: usize) {
+    let signals = (burst * ROUNDS) as u32;
+    println!(
+        "{}: {} signals in {:?} ({:?}/signal), {} calls ({:.2} calls/signal)",
+        name,
+        signals,
+        elapsed,
+        elapsed / signals,
+        calls,
+        calls as f64 / signals as f64,
+    );
+}
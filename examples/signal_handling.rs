@@ -39,6 +39,8 @@ fn main() -> io::Result<()> {
                         }
                         Some(Signal::User1) => println!("Got user signal 1"),
                         Some(Signal::User2) => println!("Got user signal 2"),
+                        Some(Signal::Hangup) => println!("Got hangup signal"),
+                        Some(signal) => println!("Got unexpected signal: {:?}", signal),
                         None => break, // No more signals.
                     }
                 },
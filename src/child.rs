@@ -0,0 +1,591 @@
+//! Watching child processes, see [`ChildWatcher`].
+
+use std::collections::VecDeque;
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Child, ExitStatus};
+use std::time::Duration;
+use std::{fmt, io, mem};
+
+use mio::{Interest, Registry, Token, event};
+
+use crate::{Signal, sys};
+
+/// Notification of terminated child processes.
+///
+/// `ChildWatcher` watches a number of child processes, see
+/// [`ChildWatcher::watch`], and returns an [`ExitedChild`] for each one of
+/// them that terminates. Only the watched child processes are reaped, other
+/// child processes are left alone, see [`reap_children`] to reap all child
+/// processes.
+///
+/// [`reap_children`]: crate::reap_children
+///
+/// # Notes
+///
+/// On Android and Linux this uses [`pidfd_open(2)`] (Linux 5.3+), which
+/// doesn't use any signals.
+///
+/// On other platforms, or if `pidfd_open(2)` is not supported, this falls back
+/// to waiting for [`Signal::Child`], using the same mechanism as [`Signals`].
+/// This means that the same notes about multithreaded processes apply and that
+/// the `ChildWatcher` must be created **before** spawning the child processes,
+/// otherwise the termination of a child process could be missed. Using another
+/// `Signals` instance, as long as it doesn't listen for `Signal::Child`, is
+/// fine.
+///
+/// In both cases the child processes are reaped using [`wait4(2)`]. This means
+/// that [`Child::wait`] can't be used for the watched child processes, it will
+/// return an error if the child process was already reaped.
+///
+/// [`pidfd_open(2)`]: https://man7.org/linux/man-pages/man2/pidfd_open.2.html
+/// [`Signals`]: crate::Signals
+/// [`wait4(2)`]: https://man7.org/linux/man-pages/man2/wait4.2.html
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> std::io::Result<()> {
+/// use std::process::Command;
+///
+/// use mio::{Events, Interest, Poll, Token};
+/// use mio_signals::ChildWatcher;
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let mut watcher = ChildWatcher::new()?;
+/// poll.registry().register(&mut watcher, Token(0), Interest::READABLE)?;
+///
+/// let children = [
+///     Command::new("true").spawn()?,
+///     Command::new("false").spawn()?,
+/// ];
+/// for child in &children {
+///     watcher.watch(child)?;
+/// }
+///
+/// while !watcher.is_empty() {
+///     poll.poll(&mut events, None)?;
+///     // Same as with `Signals` we need to keep calling `receive` until it
+///     // returns `Ok(None)`.
+///     while let Some(child) = watcher.receive()? {
+///         println!("child process {} exited: {}", child.pid(), child.status());
+///         # assert!(children.iter().any(|c| c.id() == child.pid()));
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ChildWatcher {
+    backend: Backend,
+    /// Watched child processes.
+    children: Vec<Watched>,
+    /// Reaped child processes not yet returned by `receive`.
+    exited: VecDeque<ExitedChild>,
+}
+
+/// Mechanism used to get notified of terminated child processes.
+#[derive(Debug)]
+enum Backend {
+    /// `epoll(7)` instance containing a pidfd for each watched child process.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    PidFd(pidfd::Epoll),
+    /// `Signals` listening for `SIGCHLD`.
+    Signal(sys::Signals),
+}
+
+/// A watched child process.
+#[derive(Debug)]
+struct Watched {
+    pid: u32,
+    /// Only used by `Backend::PidFd`, the file descriptor is part of the
+    /// `epoll` instance until it's dropped.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    _pidfd: Option<crate::PidFd>,
+}
+
+impl ChildWatcher {
+    /// Create a new `ChildWatcher`, not watching any child processes.
+    pub fn new() -> io::Result<ChildWatcher> {
+        Ok(ChildWatcher {
+            backend: Backend::new()?,
+            children: Vec::new(),
+            exited: VecDeque::new(),
+        })
+    }
+
+    /// Watch the `child` process.
+    ///
+    /// See [`ChildWatcher::watch_pid`].
+    pub fn watch(&mut self, child: &Child) -> io::Result<()> {
+        self.watch_pid(child.id())
+    }
+
+    /// Watch the child process with `pid`.
+    ///
+    /// Returns an error if `pid` is not a child process of the calling process
+    /// (or if it was already reaped). Watching the same child process twice
+    /// does nothing.
+    pub fn watch_pid(&mut self, pid: u32) -> io::Result<()> {
+        if self.children.iter().any(|child| child.pid == pid) {
+            return Ok(());
+        }
+        check_child(pid)?;
+
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        let pidfd = match &self.backend {
+            Backend::PidFd(epoll) => Some(epoll.add(pid)?),
+            Backend::Signal(_) => None,
+        };
+        self.children.push(Watched {
+            pid,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            _pidfd: pidfd,
+        });
+        Ok(())
+    }
+
+    /// Stop watching the child process with `pid`.
+    ///
+    /// Returns `false` if the child process wasn't watched. Note that the
+    /// child process is not reaped, nor is it returned by
+    /// [`ChildWatcher::receive`] if it was already reaped.
+    pub fn unwatch(&mut self, pid: u32) -> bool {
+        match self.children.iter().position(|child| child.pid == pid) {
+            Some(idx) => {
+                let _ = self.children.swap_remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the number of watched child processes.
+    ///
+    /// This includes the child processes that terminated, but are not yet
+    /// returned by [`ChildWatcher::receive`].
+    pub fn len(&self) -> usize {
+        self.children.len() + self.exited.len()
+    }
+
+    /// Returns `true` if no child processes are watched, see
+    /// [`ChildWatcher::len`].
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Receive a terminated child process.
+    ///
+    /// This returns `Ok(None)` if none of the watched child processes have
+    /// terminated. Because Mio uses edge triggers this must be called until it
+    /// returns `Ok(None)`. Once returned the child process is no longer
+    /// watched.
+    pub fn receive(&mut self) -> io::Result<Option<ExitedChild>> {
+        if self.exited.is_empty() {
+            self.backend.clear()?;
+            self.reap()?;
+        }
+        Ok(self.exited.pop_front())
+    }
+
+    /// Receive the terminated child process with `pid`, leaving other
+    /// terminated child processes to be returned by [`ChildWatcher::receive`].
+    pub(crate) fn receive_pid(&mut self, pid: u32) -> io::Result<Option<ExitedChild>> {
+        self.backend.clear()?;
+        self.reap()?;
+        match self.exited.iter().position(|exited| exited.pid == pid) {
+            Some(idx) => Ok(self.exited.remove(idx)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns `true` if the child process with `pid` is watched, or
+    /// terminated but not yet received.
+    pub(crate) fn is_watching(&self, pid: u32) -> bool {
+        self.children.iter().any(|child| child.pid == pid)
+            || self.exited.iter().any(|exited| exited.pid == pid)
+    }
+
+    /// Reap all terminated child processes that are watched.
+    fn reap(&mut self) -> io::Result<()> {
+        let mut idx = 0;
+        while let Some(child) = self.children.get(idx) {
+            match wait4(child.pid) {
+                Ok(Some(exited)) => {
+                    self.exited.push_back(exited);
+                    let _ = self.children.swap_remove(idx);
+                }
+                Ok(None) => idx += 1,
+                // The child process was reaped by someone else, e.g. using
+                // `reap_children`.
+                Err(ref err) if err.raw_os_error() == Some(libc::ECHILD) => {
+                    let _ = self.children.swap_remove(idx);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl event::Source for ChildWatcher {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        match &mut self.backend {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Backend::PidFd(epoll) => epoll.register(registry, token, interests),
+            Backend::Signal(signals) => signals.register(registry, token, interests),
+        }
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        match &mut self.backend {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Backend::PidFd(epoll) => epoll.reregister(registry, token, interests),
+            Backend::Signal(signals) => signals.reregister(registry, token, interests),
+        }
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        match &mut self.backend {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Backend::PidFd(epoll) => epoll.deregister(registry),
+            Backend::Signal(signals) => signals.deregister(registry),
+        }
+    }
+}
+
+impl Backend {
+    /// Use pidfds if supported, falling back to `SIGCHLD` otherwise.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn new() -> io::Result<Backend> {
+        if pidfd::is_supported() {
+            pidfd::Epoll::new().map(Backend::PidFd)
+        } else {
+            Backend::signal(crate::Backend::default())
+        }
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    fn new() -> io::Result<Backend> {
+        Backend::signal(crate::Backend::default())
+    }
+
+    fn signal(backend: crate::Backend) -> io::Result<Backend> {
+        sys::Signals::new(Signal::Child.into(), backend).map(Backend::Signal)
+    }
+
+    /// Clear the readiness, so that we get a new event once another child
+    /// process terminates.
+    fn clear(&mut self) -> io::Result<()> {
+        match self {
+            // NOTE: the pidfds of the terminated child processes are removed
+            // once they are reaped.
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Backend::PidFd(_) => Ok(()),
+            Backend::Signal(signals) => signals.drain(|_, _| {}),
+        }
+    }
+}
+
+/// Returns an error if `pid` is not a child process, without reaping it.
+fn check_child(pid: u32) -> io::Result<()> {
+    let pid = match libc::id_t::try_from(pid) {
+        Ok(pid) if pid > 0 => pid,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid process id",
+            ));
+        }
+    };
+    let mut info: libc::siginfo_t = unsafe { mem::zeroed() };
+    let options = libc::WEXITED | libc::WNOHANG | libc::WNOWAIT;
+    loop {
+        if unsafe { libc::waitid(libc::P_PID, pid, &mut info, options) } == -1 {
+            match io::Error::last_os_error() {
+                ref err if err.kind() == io::ErrorKind::Interrupted => continue,
+                err => return Err(err),
+            }
+        }
+        return Ok(());
+    }
+}
+
+/// Reap the child process with `pid`, if it terminated.
+fn wait4(pid: u32) -> io::Result<Option<ExitedChild>> {
+    let mut status = 0;
+    let mut usage: libc::rusage = unsafe { mem::zeroed() };
+    loop {
+        match unsafe { libc::wait4(pid as libc::pid_t, &mut status, libc::WNOHANG, &mut usage) } {
+            0 => return Ok(None),
+            -1 => match io::Error::last_os_error() {
+                ref err if err.kind() == io::ErrorKind::Interrupted => continue,
+                err => return Err(err),
+            },
+            _ => {
+                return Ok(Some(ExitedChild {
+                    pid,
+                    status: ExitStatus::from_raw(status),
+                    usage: ResourceUsage::from_raw(&usage),
+                }));
+            }
+        }
+    }
+}
+
+/// Terminated child process, returned by [`ChildWatcher::receive`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ExitedChild {
+    pid: u32,
+    status: ExitStatus,
+    usage: ResourceUsage,
+}
+
+impl ExitedChild {
+    /// Process id of the child process.
+    pub const fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Exit status of the child process.
+    pub const fn status(&self) -> ExitStatus {
+        self.status
+    }
+
+    /// Resources used by the child process.
+    pub const fn usage(&self) -> ResourceUsage {
+        self.usage
+    }
+}
+
+/// Resources used by a process, see [`ExitedChild::usage`].
+///
+/// See [`getrusage(2)`] for a description of the fields.
+///
+/// [`getrusage(2)`]: https://man7.org/linux/man-pages/man2/getrusage.2.html
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct ResourceUsage {
+    user_time: Duration,
+    system_time: Duration,
+    max_rss: u64,
+    minor_faults: u64,
+    major_faults: u64,
+    voluntary_context_switches: u64,
+    involuntary_context_switches: u64,
+}
+
+impl ResourceUsage {
+    fn from_raw(usage: &libc::rusage) -> ResourceUsage {
+        // NOTE: all fields are `long`s, which are never negative.
+        ResourceUsage {
+            user_time: duration(usage.ru_utime),
+            system_time: duration(usage.ru_stime),
+            #[cfg(any(target_os = "ios", target_os = "macos"))]
+            max_rss: usage.ru_maxrss as u64,
+            // Kilobytes rather than bytes.
+            #[cfg(not(any(target_os = "ios", target_os = "macos")))]
+            max_rss: usage.ru_maxrss as u64 * 1024,
+            minor_faults: usage.ru_minflt as u64,
+            major_faults: usage.ru_majflt as u64,
+            voluntary_context_switches: usage.ru_nvcsw as u64,
+            involuntary_context_switches: usage.ru_nivcsw as u64,
+        }
+    }
+
+    /// Time spent executing in user mode.
+    pub const fn user_time(&self) -> Duration {
+        self.user_time
+    }
+
+    /// Time spent executing in kernel mode.
+    pub const fn system_time(&self) -> Duration {
+        self.system_time
+    }
+
+    /// Maximum resident set size, in bytes.
+    pub const fn max_rss(&self) -> u64 {
+        self.max_rss
+    }
+
+    /// Number of page faults serviced without any I/O.
+    pub const fn minor_faults(&self) -> u64 {
+        self.minor_faults
+    }
+
+    /// Number of page faults that required I/O.
+    pub const fn major_faults(&self) -> u64 {
+        self.major_faults
+    }
+
+    /// Number of times the process voluntarily gave up the processor, e.g.
+    /// to wait for I/O.
+    pub const fn voluntary_context_switches(&self) -> u64 {
+        self.voluntary_context_switches
+    }
+
+    /// Number of times the process was preempted.
+    pub const fn involuntary_context_switches(&self) -> u64 {
+        self.involuntary_context_switches
+    }
+}
+
+impl fmt::Debug for ResourceUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResourceUsage")
+            .field("user_time", &self.user_time)
+            .field("system_time", &self.system_time)
+            .field("max_rss", &self.max_rss)
+            .finish_non_exhaustive()
+    }
+}
+
+fn duration(time: libc::timeval) -> Duration {
+    Duration::new(time.tv_sec as u64, time.tv_usec as u32 * 1000)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod pidfd {
+    use std::io;
+    use std::os::unix::io::{AsRawFd, RawFd};
+
+    use log::error;
+    use mio::unix::SourceFd;
+    use mio::{Interest, Registry, Token, event};
+
+    use crate::PidFd;
+
+    /// Returns `true` if `pidfd_open(2)` is supported.
+    ///
+    /// Any error is treated as not supported, e.g. a seccomp filter could
+    /// return `EPERM` rather than `ENOSYS`.
+    pub(super) fn is_supported() -> bool {
+        PidFd::open(std::process::id()).is_ok()
+    }
+
+    /// `epoll(7)` instance, used to combine the pidfds into a single file
+    /// descriptor.
+    #[derive(Debug)]
+    pub(super) struct Epoll {
+        fd: RawFd,
+    }
+
+    impl Epoll {
+        pub(super) fn new() -> io::Result<Epoll> {
+            let fd = unsafe { libc::epoll_create1(libc::EPOLL_CLOEXEC) };
+            if fd == -1 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(Epoll { fd })
+            }
+        }
+
+        /// Open a pidfd for `pid` and add it to the `epoll` instance.
+        ///
+        /// The pidfd is removed once it's dropped.
+        pub(super) fn add(&self, pid: u32) -> io::Result<PidFd> {
+            let pidfd = PidFd::open(pid)?;
+            let mut event = libc::epoll_event {
+                events: libc::EPOLLIN as u32,
+                u64: u64::from(pid),
+            };
+            let res = unsafe {
+                libc::epoll_ctl(self.fd, libc::EPOLL_CTL_ADD, pidfd.as_raw_fd(), &mut event)
+            };
+            if res == -1 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(pidfd)
+            }
+        }
+    }
+
+    impl event::Source for Epoll {
+        fn register(
+            &mut self,
+            registry: &Registry,
+            token: Token,
+            interests: Interest,
+        ) -> io::Result<()> {
+            SourceFd(&self.fd).register(registry, token, interests)
+        }
+
+        fn reregister(
+            &mut self,
+            registry: &Registry,
+            token: Token,
+            interests: Interest,
+        ) -> io::Result<()> {
+            SourceFd(&self.fd).reregister(registry, token, interests)
+        }
+
+        fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+            SourceFd(&self.fd).deregister(registry)
+        }
+    }
+
+    impl Drop for Epoll {
+        fn drop(&mut self) {
+            if unsafe { libc::close(self.fd) } == -1 {
+                let err = io::Error::last_os_error();
+                error!("error closing ChildWatcher: {}", err);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signal_backend() {
+        use std::process::Command;
+
+        use mio::{Events, Poll};
+
+        // NOTE: the test harness runs the tests on their own thread, so the
+        // self-pipe is used rather than blocking `SIGCHLD`.
+        let mut watcher = ChildWatcher {
+            backend: Backend::signal(crate::Backend::SelfPipe).unwrap(),
+            children: Vec::new(),
+            exited: VecDeque::new(),
+        };
+        let mut poll = Poll::new().unwrap();
+        let mut events = Events::with_capacity(8);
+        poll.registry()
+            .register(&mut watcher, Token(0), Interest::READABLE)
+            .unwrap();
+
+        let pid = Command::new("sh")
+            .args(["-c", "exit 3"])
+            .spawn()
+            .map(|child| child.id())
+            .unwrap();
+        watcher.watch_pid(pid).unwrap();
+        let exit = loop {
+            if let Some(exit) = watcher.receive().unwrap() {
+                break exit;
+            }
+            // NOTE: the signal handler can interrupt the poll call.
+            match poll.poll(&mut events, Some(Duration::from_secs(1))) {
+                Ok(()) => assert!(!events.is_empty(), "missing child process exit"),
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => panic!("unexpected error: {err}"),
+            }
+        };
+        assert_eq!(exit.pid(), pid);
+        assert_eq!(exit.status().code(), Some(3));
+        assert!(watcher.is_empty());
+    }
+}
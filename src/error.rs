@@ -0,0 +1,79 @@
+//! Error type for sending signals, see [`SendError`].
+
+use std::{error, fmt, io};
+
+/// Error returned when sending a signal fails.
+///
+/// This can be converted into an [`io::Error`], which means the `?` operator
+/// can be used in functions that return [`io::Result`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SendError {
+    /// The process, process group or thread doesn't exist (`ESRCH`).
+    NoSuchProcess,
+    /// The caller doesn't have permission to send the signal to the process,
+    /// or to any of the processes in the process group (`EPERM`).
+    PermissionDenied,
+    /// The signal is invalid or not supported on this platform (`EINVAL`).
+    InvalidSignal,
+    /// The process, process group or thread id is invalid, e.g. zero.
+    InvalidTarget,
+    /// Any other error.
+    Other(io::Error),
+}
+
+impl SendError {
+    /// The message used for the `Display` implementation.
+    const fn description(&self) -> &'static str {
+        match self {
+            SendError::NoSuchProcess => "no such process",
+            SendError::PermissionDenied => "permission denied",
+            SendError::InvalidSignal => "invalid signal",
+            SendError::InvalidTarget => "invalid process id",
+            SendError::Other(_) => "",
+        }
+    }
+}
+
+impl From<io::Error> for SendError {
+    fn from(err: io::Error) -> SendError {
+        match err.raw_os_error() {
+            Some(libc::ESRCH) => SendError::NoSuchProcess,
+            Some(libc::EPERM) => SendError::PermissionDenied,
+            Some(libc::EINVAL) => SendError::InvalidSignal,
+            _ => SendError::Other(err),
+        }
+    }
+}
+
+impl From<SendError> for io::Error {
+    fn from(err: SendError) -> io::Error {
+        match err {
+            SendError::NoSuchProcess => io::Error::from_raw_os_error(libc::ESRCH),
+            SendError::PermissionDenied => io::Error::from_raw_os_error(libc::EPERM),
+            SendError::InvalidSignal => io::Error::from_raw_os_error(libc::EINVAL),
+            SendError::InvalidTarget => {
+                io::Error::new(io::ErrorKind::InvalidInput, err.description())
+            }
+            SendError::Other(err) => err,
+        }
+    }
+}
+
+impl fmt::Display for SendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendError::Other(err) => err.fmt(f),
+            err => f.write_str(err.description()),
+        }
+    }
+}
+
+impl error::Error for SendError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            SendError::Other(err) => Some(err),
+            _ => None,
+        }
+    }
+}
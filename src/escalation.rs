@@ -0,0 +1,261 @@
+//! Terminating processes, see [`terminate_with_escalation`].
+
+use std::io;
+use std::process::ExitStatus;
+use std::time::{Duration, Instant};
+
+use mio::{Events, Interest, Poll, Registry, Token, event};
+
+use crate::{ChildWatcher, SendableSignal, send_signal};
+
+/// Terminate the child process with `pid`, escalating through `steps`.
+///
+/// This sends the first signal in `steps` to the process and waits up to
+/// `timeout` for it to terminate. If it doesn't terminate in time the next
+/// signal is send, and so on. If the process still hasn't terminated
+/// `timeout` after sending the last signal this returns an error of kind
+/// [`io::ErrorKind::TimedOut`].
+///
+/// The process must be a child process of the calling process, as it's
+/// reaped once terminated. See [`Escalation`] for a non-blocking version,
+/// which also describes how the process is waited on.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> std::io::Result<()> {
+/// use std::os::unix::process::ExitStatusExt;
+/// use std::process::Command;
+/// use std::time::Duration;
+///
+/// use mio_signals::{SendableSignal, Signal, terminate_with_escalation};
+///
+/// let child = Command::new("sleep").arg("10").spawn()?;
+///
+/// let steps = [Signal::Terminate.into(), SendableSignal::Kill];
+/// let termination = terminate_with_escalation(child.id(), &steps, Duration::from_secs(5))?;
+/// assert_eq!(termination.status().signal(), Some(libc::SIGTERM));
+/// assert_eq!(termination.step(), 0);
+/// # Ok(())
+/// # }
+/// ```
+pub fn terminate_with_escalation(
+    pid: u32,
+    steps: &[SendableSignal],
+    timeout: Duration,
+) -> io::Result<Termination> {
+    let mut poll = Poll::new()?;
+    let mut events = Events::with_capacity(1);
+    let mut escalation = Escalation::new(pid, steps, timeout)?;
+    poll.registry()
+        .register(&mut escalation, Token(0), Interest::READABLE)?;
+    loop {
+        if let Some(termination) = escalation.poll()? {
+            return Ok(termination);
+        }
+        match poll.poll(&mut events, Some(escalation.timeout())) {
+            Ok(()) => {}
+            Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Non-blocking version of [`terminate_with_escalation`].
+///
+/// `Escalation` implements [`event::Source`], it becomes readable once the
+/// process terminates. Because Mio doesn't support timers the caller is
+/// responsible for calling [`Escalation::poll`] once the current step timed
+/// out, see [`Escalation::deadline`] and [`Escalation::timeout`].
+///
+/// # Notes
+///
+/// This uses a [`ChildWatcher`] to wait for the process to terminate, which
+/// uses a pidfd if supported, and `SIGCHLD` and [`wait4(2)`] otherwise. See
+/// [`ChildWatcher`] for the limitations of using `SIGCHLD`.
+///
+/// [`wait4(2)`]: https://man7.org/linux/man-pages/man2/wait4.2.html
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> std::io::Result<()> {
+/// use std::process::Command;
+/// use std::time::Duration;
+///
+/// use mio::{Events, Interest, Poll, Token};
+/// use mio_signals::{Escalation, SendableSignal, Signal};
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let child = Command::new("sleep").arg("10").spawn()?;
+///
+/// let steps = [Signal::Terminate.into(), SendableSignal::Kill];
+/// let mut escalation = Escalation::new(child.id(), &steps, Duration::from_secs(5))?;
+/// poll.registry().register(&mut escalation, Token(0), Interest::READABLE)?;
+///
+/// let termination = loop {
+///     if let Some(termination) = escalation.poll()? {
+///         break termination;
+///     }
+///     poll.poll(&mut events, Some(escalation.timeout()))?;
+/// };
+/// println!("process terminated: {}", termination.status());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Escalation {
+    watcher: ChildWatcher,
+    pid: u32,
+    steps: Vec<SendableSignal>,
+    timeout: Duration,
+    /// Index of the last signal send.
+    step: usize,
+    /// Deadline of the current step.
+    deadline: Instant,
+}
+
+impl Escalation {
+    /// Start terminating the child process with `pid`, sending the first
+    /// signal in `steps`.
+    ///
+    /// Returns an error if `steps` is empty or if `pid` is not a child process
+    /// of the calling process.
+    pub fn new(pid: u32, steps: &[SendableSignal], timeout: Duration) -> io::Result<Escalation> {
+        let Some(first) = steps.first() else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "no escalation steps",
+            ));
+        };
+        let mut watcher = ChildWatcher::new()?;
+        watcher.watch_pid(pid)?;
+        send_signal(pid, *first)?;
+        Ok(Escalation {
+            watcher,
+            pid,
+            steps: steps.to_vec(),
+            timeout,
+            step: 0,
+            deadline: Instant::now() + timeout,
+        })
+    }
+
+    /// Returns the index of the last signal send.
+    pub const fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Returns the deadline of the current step, after which
+    /// [`Escalation::poll`] must be called to take the next step.
+    pub const fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Returns the time left until [`Escalation::deadline`], to be used as
+    /// timeout in [`Poll::poll`].
+    pub fn timeout(&self) -> Duration {
+        self.deadline.saturating_duration_since(Instant::now())
+    }
+
+    /// Check if the process terminated, sending the next signal if the current
+    /// step timed out.
+    ///
+    /// This must be called when `Escalation` is readable and once the
+    /// [`Escalation::deadline`] has passed. Returns `Ok(None)` if the process
+    /// hasn't terminated yet.
+    ///
+    /// Returns an error of kind [`io::ErrorKind::TimedOut`] if the last step
+    /// timed out.
+    pub fn poll(&mut self) -> io::Result<Option<Termination>> {
+        if let Some(exit) = self.watcher.receive()? {
+            return Ok(Some(Termination {
+                pid: self.pid,
+                status: exit.status(),
+                step: self.step,
+                signal: self.steps[self.step],
+            }));
+        } else if self.watcher.is_empty() {
+            // NOTE: we can't send any more signals as the process id can be
+            // reused by now.
+            return Err(io::Error::other("process was reaped by someone else"));
+        }
+
+        let now = Instant::now();
+        if now < self.deadline {
+            return Ok(None);
+        }
+        match self.steps.get(self.step + 1) {
+            Some(signal) => {
+                send_signal(self.pid, *signal)?;
+                self.step += 1;
+                self.deadline = now + self.timeout;
+                Ok(None)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "process didn't terminate after the last escalation step",
+            )),
+        }
+    }
+}
+
+impl event::Source for Escalation {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.watcher.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.watcher.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.watcher.deregister(registry)
+    }
+}
+
+/// How a process was terminated, see [`terminate_with_escalation`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Termination {
+    pid: u32,
+    status: ExitStatus,
+    step: usize,
+    signal: SendableSignal,
+}
+
+impl Termination {
+    /// Process id of the terminated process.
+    pub const fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Exit status of the process.
+    pub const fn status(&self) -> ExitStatus {
+        self.status
+    }
+
+    /// Index of the last signal send before the process terminated.
+    pub const fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Last signal send before the process terminated.
+    ///
+    /// Note that the process could have terminated for another reason, see
+    /// [`Termination::status`].
+    pub const fn signal(&self) -> SendableSignal {
+        self.signal
+    }
+}
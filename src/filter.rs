@@ -0,0 +1,116 @@
+//! Filtering of signals based on their sender.
+
+use std::{fmt, process};
+
+use crate::SignalInfo;
+
+/// Filter for the sender of signals, see [`Signals::set_sender_filter`].
+///
+/// The filter only applies to signals send by other processes, i.e. signals
+/// for which [`SignalOrigin::is_process`] returns `true`. Signals send by the
+/// kernel (e.g. [`Signal::Child`]), the terminal driver (e.g. ctrl-c) or by
+/// the process itself are always accepted.
+///
+/// [`Signals::set_sender_filter`]: crate::Signals::set_sender_filter
+/// [`SignalOrigin::is_process`]: crate::SignalOrigin::is_process
+/// [`Signal::Child`]: crate::Signal::Child
+#[non_exhaustive]
+pub enum SenderFilter {
+    /// Only accept signals send by processes running as one of the user ids
+    /// (see [`SignalInfo::uid`]).
+    Uids(Vec<u32>),
+    /// Only accept signals send by one of the processes (see
+    /// [`SignalInfo::pid`]).
+    Pids(Vec<u32>),
+    /// Only accept signals for which the function returns `true`.
+    Predicate(Box<dyn Fn(&SignalInfo) -> bool + Send + Sync>),
+}
+
+impl SenderFilter {
+    /// Create a new [`SenderFilter::Predicate`].
+    pub fn predicate<F>(predicate: F) -> SenderFilter
+    where
+        F: Fn(&SignalInfo) -> bool + Send + Sync + 'static,
+    {
+        SenderFilter::Predicate(Box::new(predicate))
+    }
+
+    /// Returns `true` if the signal should be accepted.
+    fn accept(&self, info: &SignalInfo) -> bool {
+        if !info.origin().is_process() || info.pid() == Some(process::id()) {
+            return true;
+        }
+        match self {
+            SenderFilter::Uids(uids) => info.uid().is_some_and(|uid| uids.contains(&uid)),
+            SenderFilter::Pids(pids) => info.pid().is_some_and(|pid| pids.contains(&pid)),
+            SenderFilter::Predicate(predicate) => predicate(info),
+        }
+    }
+}
+
+impl fmt::Debug for SenderFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SenderFilter::Uids(uids) => f.debug_tuple("Uids").field(uids).finish(),
+            SenderFilter::Pids(pids) => f.debug_tuple("Pids").field(pids).finish(),
+            SenderFilter::Predicate(_) => f.debug_tuple("Predicate").finish_non_exhaustive(),
+        }
+    }
+}
+
+/// Callback for rejected signals, see `Signals::set_rejected_callback`.
+pub(crate) type RejectedCallback = Box<dyn FnMut(&SignalInfo) + Send + Sync>;
+
+/// Filter state of `Signals`.
+#[derive(Default)]
+pub(crate) struct Filter {
+    sender: Option<SenderFilter>,
+    /// Number of rejected signals.
+    rejected: u64,
+    /// Called for every rejected signal.
+    on_rejected: Option<RejectedCallback>,
+}
+
+impl Filter {
+    /// Returns `true` if a sender filter is set.
+    pub(crate) const fn is_active(&self) -> bool {
+        self.sender.is_some()
+    }
+
+    /// Replace the sender filter, returning the previous filter.
+    pub(crate) fn replace_sender(&mut self, filter: Option<SenderFilter>) -> Option<SenderFilter> {
+        std::mem::replace(&mut self.sender, filter)
+    }
+
+    pub(crate) fn set_on_rejected(&mut self, on_rejected: RejectedCallback) {
+        self.on_rejected = Some(on_rejected);
+    }
+
+    pub(crate) const fn rejected(&self) -> u64 {
+        self.rejected
+    }
+
+    /// Returns `true` if the signal should be accepted, calling the rejected
+    /// callback if not.
+    pub(crate) fn accept(&mut self, info: &SignalInfo) -> bool {
+        match &self.sender {
+            Some(filter) if !filter.accept(info) => {
+                self.rejected += 1;
+                if let Some(on_rejected) = &mut self.on_rejected {
+                    on_rejected(info);
+                }
+                false
+            }
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Debug for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Filter")
+            .field("sender", &self.sender)
+            .field("rejected", &self.rejected)
+            .finish()
+    }
+}
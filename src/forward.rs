@@ -0,0 +1,257 @@
+//! Forwarding signals to child processes, see [`SignalForwarder`].
+
+use std::io;
+use std::os::unix::process::ExitStatusExt;
+use std::process::{self, ExitStatus};
+
+use mio::{Events, Interest, Poll, Registry, Token, event};
+
+use crate::{
+    ChildWatcher, SendError, SendableSignal, Signal, SignalSet, Signals, Target, send_signal_to,
+};
+
+/// Forwards received signals to a child process (group), like [tini] or
+/// [dumb-init].
+///
+/// All signals received by the `Signals` instance are send to the target,
+/// except for [`Signal::Child`] which is about the child processes of the
+/// calling process. Signals can be rewritten before being forwarded, e.g.
+/// turning `SIGTERM` into `SIGQUIT` for a process that uses `SIGQUIT` for a
+/// graceful shutdown.
+///
+/// See [`SignalForwarder::run`] for running the forwarder until the child
+/// process exits, and [`SignalForwarder::exit`] to exit with the status of the
+/// child process. For use in an existing event loop `SignalForwarder`
+/// implements [`event::Source`], call [`SignalForwarder::forward`] when it's
+/// readable.
+///
+/// [tini]: https://github.com/krallin/tini
+/// [dumb-init]: https://github.com/Yelp/dumb-init
+///
+/// # Notes
+///
+/// The signals part of `Signals` don't terminate the calling process (see
+/// [`Signals`]), but all other signals still have their default action. Use
+/// [`SignalForwarder::default_signals`] to create a `Signals` instance that
+/// receives (and thus forwards) all signals that would otherwise terminate the
+/// calling process before the child process.
+///
+/// When using [`Backend::SelfPipe`] only a single `Signals` instance can
+/// receive a particular signal. This means that no other `Signals` instance
+/// using the self-pipe backend can be created for any of the (many) signals in
+/// [`SignalForwarder::default_signals`] while the forwarder exists. The same
+/// applies to [`Signal::Child`] if the [`ChildWatcher`] used by
+/// [`SignalForwarder::run`] falls back to using it, use
+/// [`SignalForwarder::run_with_watcher`] to use an existing `ChildWatcher`.
+///
+/// [`Backend::SelfPipe`]: crate::Backend::SelfPipe
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> {
+/// use std::process::Command;
+///
+/// use mio_signals::{SendableSignal, Signal, SignalForwarder, Signals, Target};
+///
+/// // Create `Signals` before spawning the child process, otherwise it may
+/// // miss signals.
+/// let signals = Signals::new(SignalForwarder::default_signals())?;
+/// let child = Command::new("my-server").spawn()?;
+///
+/// // `my-server` uses `SIGQUIT` for a graceful shutdown.
+/// let rewrites = [(Signal::Terminate, SendableSignal::Signal(Signal::Quit))];
+/// let mut forwarder = SignalForwarder::new(signals, Target::Process(child.id()), &rewrites);
+/// let status = forwarder.run(child.id())?;
+/// SignalForwarder::exit(status)
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct SignalForwarder {
+    signals: Signals,
+    target: Target,
+    rewrites: Vec<(Signal, SendableSignal)>,
+}
+
+impl SignalForwarder {
+    /// Create a new `SignalForwarder`, forwarding all signals received by
+    /// `signals` to `target`, after rewriting them using `rewrites`.
+    pub fn new(
+        signals: Signals,
+        target: Target,
+        rewrites: &[(Signal, SendableSignal)],
+    ) -> SignalForwarder {
+        SignalForwarder {
+            signals,
+            target,
+            rewrites: rewrites.to_vec(),
+        }
+    }
+
+    /// Returns all signals that would terminate the calling process by
+    /// default, and can be send to another process, and
+    /// [`Signal::WindowChange`].
+    ///
+    /// This doesn't include signals that are generated for the calling
+    /// process itself, such as `SIGSEGV` and [`Signal::Pipe`], or
+    /// [`Signal::Child`].
+    pub fn default_signals() -> SignalSet {
+        let mut set = SignalSet::all() | Signal::WindowChange;
+        set.extend(
+            [
+                libc::SIGALRM,
+                libc::SIGVTALRM,
+                libc::SIGPROF,
+                libc::SIGIO,
+                #[cfg(any(target_os = "linux", target_os = "android"))]
+                libc::SIGPWR,
+            ]
+            .map(Signal::Other),
+        );
+        // NOTE: not all platforms support (all) real-time signals.
+        set.extend(
+            (0..=Signal::MAX_REALTIME)
+                .map(Signal::Realtime)
+                .filter(|signal| signal.number().is_some()),
+        );
+        set
+    }
+
+    /// Forward all received signals.
+    ///
+    /// Returns the number of forwarded signals. If the target doesn't exist
+    /// (anymore) the signals are dropped, as the child process likely just
+    /// terminated.
+    ///
+    /// Because Mio uses edge triggers this receives signals until none are
+    /// left.
+    pub fn forward(&mut self) -> io::Result<usize> {
+        let mut forwarded = 0;
+        while let Some(signal) = self.signals.receive()? {
+            if signal == Signal::Child {
+                continue;
+            }
+            let signal = self
+                .rewrites
+                .iter()
+                .find(|(from, _)| *from == signal)
+                .map_or(SendableSignal::from(signal), |(_, to)| *to);
+            match send_signal_to(self.target, signal) {
+                Ok(()) => forwarded += 1,
+                Err(SendError::NoSuchProcess) => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(forwarded)
+    }
+
+    /// Forward signals until the child process with `pid` terminates,
+    /// returning its exit status.
+    ///
+    /// This reaps the child process, see [`ChildWatcher`], which means that
+    /// [`Child::wait`] can't be used for it.
+    ///
+    /// [`Child::wait`]: std::process::Child::wait
+    pub fn run(&mut self, pid: u32) -> io::Result<ExitStatus> {
+        let mut watcher = ChildWatcher::new()?;
+        self.run_with_watcher(&mut watcher, pid)
+    }
+
+    /// Same as [`SignalForwarder::run`], but using an existing `watcher` to
+    /// wait for the child process with `pid`.
+    ///
+    /// The child process is added to `watcher` if it isn't watched already.
+    /// Other child processes watched by `watcher` are left alone, they can
+    /// still be received using [`ChildWatcher::receive`] after this returns.
+    pub fn run_with_watcher(
+        &mut self,
+        watcher: &mut ChildWatcher,
+        pid: u32,
+    ) -> io::Result<ExitStatus> {
+        const SIGNALS: Token = Token(0);
+        const CHILD: Token = Token(1);
+
+        let mut poll = Poll::new()?;
+        let mut events = Events::with_capacity(2);
+        if !watcher.is_watching(pid) {
+            watcher.watch_pid(pid)?;
+        }
+        poll.registry()
+            .register(&mut self.signals, SIGNALS, Interest::READABLE)?;
+        poll.registry()
+            .register(watcher, CHILD, Interest::READABLE)?;
+
+        // NOTE: the registrations are removed when `poll` is dropped.
+        loop {
+            let _ = self.forward()?;
+            if let Some(exit) = watcher.receive_pid(pid)? {
+                return Ok(exit.status());
+            } else if !watcher.is_watching(pid) {
+                return Err(io::Error::other("process was reaped by someone else"));
+            }
+            match poll.poll(&mut events, None) {
+                Ok(()) => {}
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Exit the calling process with the exit `status` of the child process.
+    ///
+    /// If the child process was terminated by a signal this uses `128` plus
+    /// the signal number as exit code, same as most shells.
+    pub fn exit(status: ExitStatus) -> ! {
+        process::exit(exit_code(status))
+    }
+}
+
+/// Returns the exit code for the exit `status` of a child process.
+fn exit_code(status: ExitStatus) -> i32 {
+    match (status.code(), status.signal()) {
+        (Some(code), _) => code,
+        (None, Some(signal)) => 128 + signal,
+        // NOTE: we only get here if the process was stopped or continued,
+        // not if it terminated.
+        (None, None) => 1,
+    }
+}
+
+impl event::Source for SignalForwarder {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.signals.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.signals.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.signals.deregister(registry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_code() {
+        assert_eq!(exit_code(ExitStatus::from_raw(0)), 0);
+        assert_eq!(exit_code(ExitStatus::from_raw(3 << 8)), 3);
+        assert_eq!(
+            exit_code(ExitStatus::from_raw(libc::SIGTERM)),
+            128 + libc::SIGTERM
+        );
+    }
+}
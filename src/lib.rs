@@ -19,8 +19,12 @@
 //! a port to Windows please see [issue #4].
 //!
 //! [issue #4]: https://github.com/Thomasdezeeuw/mio-signals/issues/4
-
-// TODO: #[non_exhaustive] to `Signal`.
+//!
+//! ## Features
+//!
+//! * `serde`: implements `Serialize` and `Deserialize` for [`Signal`],
+//!   [`SendableSignal`] and [`SignalSet`], using the signal names (e.g.
+//!   `"SIGUSR1"`) rather than the platform specific numbers.
 
 #![warn(
     missing_debug_implementations,
@@ -34,37 +38,72 @@
 #![cfg_attr(test, deny(warnings))]
 // Disallow warnings in examples, we want to set a good example after all.
 #![doc(test(attr(deny(warnings))))]
-// `SignalSet` can never be empty, thus an `is_empty` method doesn't make sense.
-#![allow(clippy::len_without_is_empty)]
 
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
 use std::iter::FusedIterator;
-use std::num::NonZeroU8;
-use std::ops::BitOr;
-use std::{fmt, io};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Sub, SubAssign};
+use std::os::unix::thread::JoinHandleExt;
+use std::process::ExitStatus;
+use std::thread::JoinHandle;
+use std::{fmt, io, ptr};
 
 use mio::{Interest, Registry, Token, event};
 
+mod child;
+mod error;
+mod escalation;
+mod filter;
+mod forward;
+mod parse;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod pidfd;
+mod reaper;
+#[cfg(feature = "serde")]
+mod serde;
 mod sys;
 
+pub use child::{ChildWatcher, ExitedChild, ResourceUsage};
+pub use error::SendError;
+pub use escalation::{Escalation, Termination, terminate_with_escalation};
+pub use filter::SenderFilter;
+pub use forward::SignalForwarder;
+pub use parse::ParseSignalError;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub use pidfd::PidFd;
+pub use reaper::{Reaper, is_pid1};
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub use reaper::{is_child_subreaper, set_child_subreaper};
+
 /// Notification of process signals.
 ///
 /// # Multithreaded process
 ///
-/// For `Signals` to function correctly in multithreaded processes it must be
-/// created on the main thread **before** spawning any threads. This is due to
-/// an implementation detail where the spawned threads must inherit various
-/// signal related thread options from the parent thread (mainly the blocked
-/// signals on Linux).
+/// `Signals` can be created at any time, but it works best if it's created on
+/// the main thread **before** spawning any threads. On Android and Linux
+/// [`signalfd(2)`] is used in that case, which relies on the spawned threads
+/// inheriting the blocked signals from the parent thread.
 ///
-/// Any threads spawned before calling `Signals::new` will experience the
-/// default process signals behaviour, i.e. sending it a signal will stop it.
+/// If other threads already exist [`Backend::Auto`], the default, installs a
+/// signal handler instead, which forwards the signals to a pipe (see
+/// [`Backend::SelfPipe`]). The downsides are that real-time signals can be
+/// dropped if too many are received at once, that the exit status of child
+/// processes isn't available (see [`SignalInfo::child`]), and that only a
+/// single `Signals` instance can receive a particular signal; creating a second
+/// one returns an error of kind [`io::ErrorKind::AlreadyExists`].
+///
+/// Use [`SignalsBuilder::backend`] with [`Backend::SignalFd`] to always use
+/// `signalfd(2)`, in which case any threads spawned before creating `Signals`
+/// will experience the default process signals behaviour, i.e. sending it a
+/// signal will stop it.
 ///
 /// # Notes
 ///
 /// On Android and Linux this will block all signals in the signal set given
-/// when creating `Signals`, using [`pthread_sigmask(3)`]. This means that the
-/// thread in which `Signals` was created is not interrupted, or in any way
-/// notified of signal until the assiocated [`Poll`] is [polled].
+/// when creating `Signals`, using [`pthread_sigmask(3)`], or install a signal
+/// handler when using [`Backend::SelfPipe`]. This means that the thread in
+/// which `Signals` was created is not interrupted, or in any way notified of
+/// signal until the assiocated [`Poll`] is [polled].
 ///
 /// On platforms that support [`kqueue(2)`] the signal handler action is set to
 /// `SIG_IGN` using [`sigaction(2)`], meaning that all signals will be ignored.
@@ -80,7 +119,9 @@ mod sys;
 /// # Implementation notes
 ///
 /// On platforms that support [`kqueue(2)`] this will use the `EVFILT_SIGNAL`
-/// event filter. On Android and Linux it uses [`signalfd(2)`].
+/// event filter. On Android and Linux it uses [`signalfd(2)`]. On all
+/// platforms a signal handler installed using [`sigaction(2)`], that writes to
+/// a pipe, can be used instead, see [`Backend`].
 ///
 /// [`signalfd(2)`]: http://man7.org/linux/man-pages/man2/signalfd.2.html
 ///
@@ -121,6 +162,8 @@ mod sys;
 ///                         Some(Signal::Quit) => println!("Got quit signal"),
 ///                         Some(Signal::User1) => println!("Got user signal 1"),
 ///                         Some(Signal::User2) => println!("Got user signal 2"),
+///                         Some(Signal::Hangup) => println!("Got hangup signal"),
+///                         Some(signal) => println!("Got unexpected signal: {:?}", signal),
 ///                         None => break,
 ///                     }
 ///                 },
@@ -134,19 +177,311 @@ mod sys;
 #[derive(Debug)]
 pub struct Signals {
     sys: sys::Signals,
+    filter: filter::Filter,
+    /// Original action of `SIGPIPE`, restored when dropped. Must be dropped
+    /// after `sys`.
+    #[allow(dead_code)] // Only used for its `Drop` implementation.
+    pipe: Option<sys::PipeGuard>,
 }
 
 impl Signals {
     /// Create a new signal notifier.
+    ///
+    /// This uses [`Backend::Auto`], see [Multithreaded process]. If `signals`
+    /// contains [`Signal::Pipe`] this is the same as using
+    /// [`SignalsBuilder::pipe_policy`] with [`PipePolicy::Receive`]. See
+    /// [`Signals::builder`] for more options.
+    ///
+    /// [Multithreaded process]: Signals#multithreaded-process
+    ///
+    /// Returns an error if `signals` is empty.
     pub fn new(signals: SignalSet) -> io::Result<Signals> {
-        sys::Signals::new(signals).map(|sys| Signals { sys })
+        Signals::builder(signals).build()
+    }
+
+    /// Create a builder for a signal notifier, receiving `signals`.
+    ///
+    /// # Examples
+    ///
+    /// Command line tools, that for example are used in `tool | head`, likely
+    /// want to use the default action of `SIGPIPE`: terminating the process.
+    /// Libraries that can't control when they're called, e.g. after the
+    /// application spawned its threads, can use [`Backend::SelfPipe`] to
+    /// always get the same behaviour.
+    ///
+    /// ```
+    /// use mio_signals::{Backend, PipePolicy, SignalSet, Signals};
+    ///
+    /// let signals = Signals::builder(SignalSet::all())
+    ///     .pipe_policy(PipePolicy::Default)
+    ///     .backend(Backend::SelfPipe)
+    ///     .build()?;
+    /// # drop(signals);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub const fn builder(signals: SignalSet) -> SignalsBuilder {
+        SignalsBuilder {
+            signals,
+            pipe_policy: None,
+            backend: None,
+        }
     }
 
     /// Receive a signal, if any.
     ///
     /// If no signal is available this returns `Ok(None)`.
     pub fn receive(&mut self) -> io::Result<Option<Signal>> {
-        self.sys.receive()
+        if self.filter.is_active() {
+            // Need the sender information to filter the signal.
+            self.receive_info().map(|info| info.map(|info| info.signal))
+        } else {
+            self.sys.receive()
+        }
+    }
+
+    /// Receive a signal, if any, including additional information about the
+    /// signal.
+    ///
+    /// If no signal is available this returns `Ok(None)`.
+    ///
+    /// See [`SignalInfo`] for the information available.
+    pub fn receive_info(&mut self) -> io::Result<Option<SignalInfo>> {
+        loop {
+            match self.sys.receive_info()? {
+                Some(info) if !self.filter.accept(&info) => continue,
+                info => return Ok(info.map(SignalInfo::complete)),
+            }
+        }
+    }
+
+    /// Receive multiple signals at once, including additional information
+    /// about the signals.
+    ///
+    /// This receives as many signals as fit in `infos`, using a single system
+    /// call, and returns the number of signals received. If no signals are
+    /// available this returns `Ok(0)`.
+    ///
+    /// See [`SignalInfo::default`] for creating the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use mio_signals::{SignalInfo, SignalSet, Signals};
+    ///
+    /// let mut signals = Signals::new(SignalSet::all())?;
+    ///
+    /// let mut infos = [SignalInfo::default(); 16];
+    /// loop {
+    ///     let n = signals.receive_many(&mut infos)?;
+    ///     for info in &infos[..n] {
+    ///         println!("Got signal: {:?}", info.signal());
+    ///     }
+    ///     if n < infos.len() {
+    ///         // No more signals.
+    ///         break;
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn receive_many(&mut self, infos: &mut [SignalInfo]) -> io::Result<usize> {
+        let mut n = 0;
+        // If signals are rejected by the filter we try to fill the remainder
+        // of the buffer, to ensure that receiving less signals than the
+        // buffer can hold still means no more signals are available.
+        while n < infos.len() {
+            let start = n;
+            let requested = infos.len() - start;
+            let received = self.sys.receive_many(&mut infos[start..])?;
+            for i in start..start + received {
+                if self.filter.accept(&infos[i]) {
+                    infos[n] = infos[i].complete();
+                    n += 1;
+                }
+            }
+            if received < requested {
+                break;
+            }
+        }
+        Ok(n)
+    }
+
+    /// Receive all available signals, returning the number of times each
+    /// signal was received.
+    ///
+    /// This is useful if only the received signals matter, not the order in
+    /// which they were received or any additional information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> {
+    /// use std::process;
+    ///
+    /// use mio_signals::{Signal, SignalSet, Signals, send_signal};
+    ///
+    /// let mut signals = Signals::new(SignalSet::all())?;
+    /// send_signal(process::id(), Signal::User1)?;
+    ///
+    /// let counts = signals.drain()?;
+    /// assert_eq!(counts.signals(), Signal::User1.into());
+    /// assert_eq!(counts.count(Signal::User1), 1);
+    /// assert_eq!(counts.count(Signal::User2), 0);
+    ///
+    /// // All signals are received, so we're left with nothing.
+    /// assert!(signals.drain()?.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn drain(&mut self) -> io::Result<SignalCounts> {
+        let mut counts = SignalCounts::new();
+        let filter = &mut self.filter;
+        self.sys
+            .drain(|info, n| {
+                if filter.accept(&info) {
+                    counts.add(info.signal, n);
+                }
+            })
+            .map(|()| counts)
+    }
+
+    /// Set a filter for the sender of signals.
+    ///
+    /// Signals rejected by the filter are dropped when receiving them, they
+    /// are never returned by any of the receive methods (or [`drain`]).
+    /// Instead they are counted, see [`Signals::rejected`], and passed to the
+    /// callback set using [`Signals::set_rejected_callback`], if any. See
+    /// [`SenderFilter`] for which signals are filtered.
+    ///
+    /// [`drain`]: Signals::drain
+    ///
+    /// # Notes
+    ///
+    /// This is only supported on Android and Linux, on other platforms the
+    /// sender of a signal is unknown and this returns an error with kind
+    /// [`io::ErrorKind::Unsupported`].
+    ///
+    /// # Examples
+    ///
+    /// Only accept signals send by processes running as the same user.
+    ///
+    /// ```
+    /// # #[cfg(any(target_os = "linux", target_os = "android"))]
+    /// # fn main() -> std::io::Result<()> {
+    /// use mio_signals::{SenderFilter, SignalSet, Signals};
+    ///
+    /// let mut signals = Signals::new(SignalSet::all())?;
+    /// let uid = unsafe { libc::getuid() };
+    /// signals.set_sender_filter(SenderFilter::Uids(vec![uid]))?;
+    /// signals.set_rejected_callback(|info| {
+    ///     eprintln!("rejected {} send by {:?}", info.signal(), info.pid());
+    /// });
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    /// # fn main() {}
+    /// ```
+    pub fn set_sender_filter(&mut self, filter: SenderFilter) -> io::Result<()> {
+        if self.sys.sender_info() {
+            let _ = self.filter.replace_sender(Some(filter));
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "sender of signals is unknown on this platform",
+            ))
+        }
+    }
+
+    /// Remove the sender filter, returning it (if any).
+    pub fn remove_sender_filter(&mut self) -> Option<SenderFilter> {
+        self.filter.replace_sender(None)
+    }
+
+    /// Set a function that is called for every signal rejected by the sender
+    /// filter, see [`Signals::set_sender_filter`].
+    pub fn set_rejected_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut(&SignalInfo) + Send + Sync + 'static,
+    {
+        self.filter.set_on_rejected(Box::new(callback));
+    }
+
+    /// Number of signals rejected by the sender filter, see
+    /// [`Signals::set_sender_filter`].
+    pub const fn rejected(&self) -> u64 {
+        self.filter.rejected()
+    }
+}
+
+/// Builder for [`Signals`], see [`Signals::builder`].
+#[derive(Copy, Clone, Debug)]
+#[must_use = "call `build` to create `Signals`"]
+pub struct SignalsBuilder {
+    signals: SignalSet,
+    pipe_policy: Option<PipePolicy>,
+    backend: Option<Backend>,
+}
+
+impl SignalsBuilder {
+    /// Take ownership of `SIGPIPE`, using `policy`.
+    ///
+    /// See [`PipePolicy`] for the possible options. The original action of
+    /// `SIGPIPE` is restored when `Signals` is dropped. [`PipePolicy::Receive`]
+    /// adds [`Signal::Pipe`] to the signals, using the other policies while
+    /// receiving `Signal::Pipe` makes [`SignalsBuilder::build`] return an
+    /// error.
+    pub const fn pipe_policy(mut self, policy: PipePolicy) -> SignalsBuilder {
+        self.pipe_policy = Some(policy);
+        self
+    }
+
+    /// Use `backend` to receive the signals.
+    ///
+    /// See [`Backend`] for the possible options, defaults to
+    /// [`Backend::default`].
+    pub const fn backend(mut self, backend: Backend) -> SignalsBuilder {
+        self.backend = Some(backend);
+        self
+    }
+
+    /// Create the signal notifier.
+    ///
+    /// Returns an error if no signals are received.
+    pub fn build(self) -> io::Result<Signals> {
+        let mut signals = self.signals;
+        let policy = match self.pipe_policy {
+            Some(PipePolicy::Receive) => {
+                signals |= Signal::Pipe;
+                Some(PipePolicy::Receive)
+            }
+            Some(PipePolicy::Default | PipePolicy::Ignore) if signals.contains(Signal::Pipe) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "can't receive `Signal::Pipe` when not using `PipePolicy::Receive`",
+                ));
+            }
+            Some(policy) => Some(policy),
+            None if signals.contains(Signal::Pipe) => Some(PipePolicy::Receive),
+            None => None,
+        };
+        if signals.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "empty signal set",
+            ));
+        }
+
+        // NOTE: the guard must be created before `sys::Signals` so it stores
+        // the original action.
+        let pipe = policy.map(sys::PipeGuard::new).transpose()?;
+        let backend = self.backend.unwrap_or_default();
+        sys::Signals::new(signals, backend).map(|sys| Signals {
+            sys,
+            filter: filter::Filter::default(),
+            pipe,
+        })
     }
 }
 
@@ -189,26 +524,62 @@ impl event::Source for Signals {
 /// assert!(set.contains(Signal::Quit));
 /// assert!(!set.contains(Signal::Terminate));
 /// assert!(set.contains(Signal::Interrupt | Signal::Quit));
+///
+/// // The usual set operations are also supported.
+/// assert_eq!(set & Signal::Quit, Signal::Quit.into());
+/// assert_eq!(set - Signal::Quit, Signal::Interrupt.into());
+/// assert_eq!(set ^ (Signal::Quit | Signal::Terminate), Signal::Interrupt | Signal::Terminate);
 /// ```
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
-pub struct SignalSet(NonZeroU8);
+pub struct SignalSet(u128);
 
-// NOTE: these may never be zero.
-const INTERRUPT: u8 = 1;
-const QUIT: u8 = 1 << 1;
-const TERMINATE: u8 = 1 << 2;
-const USER1: u8 = 1 << 3;
-const USER2: u8 = 1 << 4;
+const INTERRUPT: u128 = 1;
+const QUIT: u128 = 1 << 1;
+const TERMINATE: u128 = 1 << 2;
+const USER1: u128 = 1 << 3;
+const USER2: u128 = 1 << 4;
+const HANGUP: u128 = 1 << 5;
+const CHILD: u128 = 1 << 6;
+const WINDOW_CHANGE: u128 = 1 << 7;
+const PIPE: u128 = 1 << 8;
+/// Bit of `Signal::Realtime(0)`, bits 32 to 64 are used for the real-time
+/// signals.
+const REALTIME_SHIFT: u32 = 32;
+/// Bit of `Signal::Other(1)`, the upper 64 bits are used for the other signals.
+const OTHER_SHIFT: u32 = 64;
 
 impl SignalSet {
+    /// Create a new empty set.
+    ///
+    /// # Notes
+    ///
+    /// [`Signals`] can't be created using an empty set.
+    pub const fn empty() -> SignalSet {
+        SignalSet(0)
+    }
+
     /// Create a new set with all signals.
+    ///
+    /// # Notes
+    ///
+    /// This doesn't include [`Signal::Child`] and [`Signal::WindowChange`], as
+    /// they don't request anything of the process, unlike the other signals.
+    /// Neither does it include [`Signal::Pipe`], as that is ignored by the Rust
+    /// runtime by default, see [`PipePolicy`]. It also doesn't include any
+    /// [`Signal::Realtime`] signals, as their meaning is defined by the
+    /// application, or any [`Signal::Other`] signals.
     pub const fn all() -> SignalSet {
-        SignalSet(unsafe { NonZeroU8::new_unchecked(INTERRUPT | QUIT | TERMINATE | USER1 | USER2) })
+        SignalSet(INTERRUPT | QUIT | TERMINATE | USER1 | USER2 | HANGUP)
     }
 
     /// Number of signals in the set.
     pub const fn len(self) -> usize {
-        self.0.get().count_ones() as usize
+        self.0.count_ones() as usize
+    }
+
+    /// Whether or not the set is empty.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
     }
 
     /// Whether or not all signals in `other` are contained within `self`.
@@ -232,47 +603,231 @@ impl SignalSet {
     where
         S: Into<SignalSet>,
     {
-        let other = other.into();
-        (self.0.get() & other.0.get()) == other.0.get()
+        other.into().is_subset(self)
+    }
+
+    /// Add `signal` to the set.
+    ///
+    /// Returns `true` if the signal wasn't yet in the set.
+    pub fn insert(&mut self, signal: Signal) -> bool {
+        let signal = SignalSet::from(signal);
+        let inserted = !self.contains(signal);
+        *self = self.union(signal);
+        inserted
+    }
+
+    /// Add `signal` to the set, if it can be part of a set.
+    ///
+    /// Returns `None` if the offset of [`Signal::Realtime`] is larger than
+    /// [`Signal::MAX_REALTIME`], or if the signal number of [`Signal::Other`]
+    /// is not within `1..=`[`Signal::MAX_OTHER`], where [`SignalSet::insert`]
+    /// would panic. Otherwise this returns `Some(true)` if the signal wasn't
+    /// yet in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio_signals::{Signal, SignalSet};
+    ///
+    /// let mut set = SignalSet::empty();
+    /// assert_eq!(set.try_insert(Signal::Interrupt), Some(true));
+    /// assert_eq!(set.try_insert(Signal::Interrupt), Some(false));
+    /// assert_eq!(set.try_insert(Signal::Other(0)), None);
+    /// assert_eq!(set, Signal::Interrupt.into());
+    /// ```
+    pub fn try_insert(&mut self, signal: Signal) -> Option<bool> {
+        let signal = SignalSet::from_signal(signal)?;
+        let inserted = !self.contains(signal);
+        *self = self.union(signal);
+        Some(inserted)
+    }
+
+    /// Returns a set with only `signal`, or `None` if it can't be part of a
+    /// set, see [`SignalSet::try_insert`].
+    fn from_signal(signal: Signal) -> Option<SignalSet> {
+        Some(SignalSet(match signal {
+            Signal::Interrupt => INTERRUPT,
+            Signal::Quit => QUIT,
+            Signal::Terminate => TERMINATE,
+            Signal::User1 => USER1,
+            Signal::User2 => USER2,
+            Signal::Hangup => HANGUP,
+            Signal::Child => CHILD,
+            Signal::WindowChange => WINDOW_CHANGE,
+            Signal::Pipe => PIPE,
+            Signal::Realtime(offset) if offset <= Signal::MAX_REALTIME => {
+                1 << (REALTIME_SHIFT + offset as u32)
+            }
+            Signal::Realtime(_) => return None,
+            Signal::Other(raw_signal) => match sys::from_raw_signal(raw_signal) {
+                Signal::Other(raw_signal) if (1..=Signal::MAX_OTHER).contains(&raw_signal) => {
+                    1 << (OTHER_SHIFT + raw_signal as u32 - 1)
+                }
+                Signal::Other(_) => return None,
+                // Use the bit of the signal with its own variant.
+                signal => return SignalSet::from_signal(signal),
+            },
+        }))
+    }
+
+    /// Remove `signal` from the set.
+    ///
+    /// Returns `true` if the signal was in the set.
+    pub fn remove(&mut self, signal: Signal) -> bool {
+        let signal = SignalSet::from(signal);
+        let removed = self.contains(signal);
+        *self = self.difference(signal);
+        removed
+    }
+
+    /// Returns a set with all signals in either `self` or `other`, same as
+    /// `self | other`.
+    pub const fn union(self, other: SignalSet) -> SignalSet {
+        SignalSet(self.0 | other.0)
+    }
+
+    /// Returns a set with all signals in both `self` and `other`, same as
+    /// `self & other`.
+    pub const fn intersection(self, other: SignalSet) -> SignalSet {
+        SignalSet(self.0 & other.0)
+    }
+
+    /// Returns a set with all signals in `self`, but not in `other`, same as
+    /// `self - other`.
+    pub const fn difference(self, other: SignalSet) -> SignalSet {
+        SignalSet(self.0 & !other.0)
+    }
+
+    /// Returns a set with all signals in either `self` or `other`, but not in
+    /// both, same as `self ^ other`.
+    pub const fn symmetric_difference(self, other: SignalSet) -> SignalSet {
+        SignalSet(self.0 ^ other.0)
+    }
+
+    /// Returns a set with all signals in [`SignalSet::all`] that are not in
+    /// `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio_signals::{Signal, SignalSet};
+    ///
+    /// let set = Signal::Interrupt | Signal::Terminate | Signal::Child;
+    /// assert_eq!(set.complement(), Signal::Quit | Signal::User1 | Signal::User2 | Signal::Hangup);
+    /// assert_eq!(SignalSet::all().complement(), SignalSet::empty());
+    /// ```
+    pub const fn complement(self) -> SignalSet {
+        SignalSet::all().difference(self)
+    }
+
+    /// Whether or not all signals in `self` are also in `other`.
+    pub const fn is_subset(self, other: SignalSet) -> bool {
+        (self.0 & other.0) == self.0
+    }
+
+    /// Whether or not all signals in `other` are also in `self`.
+    pub const fn is_superset(self, other: SignalSet) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Whether or not `self` and `other` have no signals in common.
+    pub const fn is_disjoint(self, other: SignalSet) -> bool {
+        (self.0 & other.0) == 0
     }
 }
 
+/// # Panics
+///
+/// This panics if the offset of [`Signal::Realtime`] is larger than
+/// [`Signal::MAX_REALTIME`], or if the signal number of [`Signal::Other`] is
+/// not within `1..=`[`Signal::MAX_OTHER`], see [`SignalSet::try_insert`]
+/// for a fallible alternative.
 impl From<Signal> for SignalSet {
     fn from(signal: Signal) -> Self {
-        SignalSet(unsafe {
-            NonZeroU8::new_unchecked(match signal {
-                Signal::Interrupt => INTERRUPT,
-                Signal::Quit => QUIT,
-                Signal::Terminate => TERMINATE,
-                Signal::User1 => USER1,
-                Signal::User2 => USER2,
-            })
-        })
+        match SignalSet::from_signal(signal) {
+            Some(set) => set,
+            None if matches!(signal, Signal::Realtime(_)) => {
+                panic!("real-time signal offset too large")
+            }
+            None => panic!("signal number out of range"),
+        }
     }
 }
 
-impl BitOr for SignalSet {
-    type Output = SignalSet;
+impl FromIterator<Signal> for SignalSet {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = Signal>,
+    {
+        let mut set = SignalSet::empty();
+        set.extend(iter);
+        set
+    }
+}
 
-    fn bitor(self, rhs: Self) -> Self {
-        SignalSet(unsafe { NonZeroU8::new_unchecked(self.0.get() | rhs.0.get()) })
+impl Extend<Signal> for SignalSet {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = Signal>,
+    {
+        for signal in iter {
+            let _ = self.insert(signal);
+        }
     }
 }
 
-impl BitOr<Signal> for SignalSet {
-    type Output = SignalSet;
+/// Implements the set operator `$trait` for `SignalSet` using `$method`, for
+/// both `SignalSet` and `Signal` as right hand side, and the assignment
+/// variant `$assign_trait`.
+macro_rules! set_operator {
+    ($trait: ident, $fn: ident, $assign_trait: ident, $assign_fn: ident, $method: ident) => {
+        impl $trait for SignalSet {
+            type Output = SignalSet;
 
-    fn bitor(self, rhs: Signal) -> Self {
-        self | Into::<SignalSet>::into(rhs)
-    }
+            fn $fn(self, rhs: Self) -> Self {
+                self.$method(rhs)
+            }
+        }
+
+        impl $trait<Signal> for SignalSet {
+            type Output = SignalSet;
+
+            fn $fn(self, rhs: Signal) -> Self {
+                self.$method(rhs.into())
+            }
+        }
+
+        impl $assign_trait for SignalSet {
+            fn $assign_fn(&mut self, rhs: Self) {
+                *self = self.$method(rhs);
+            }
+        }
+
+        impl $assign_trait<Signal> for SignalSet {
+            fn $assign_fn(&mut self, rhs: Signal) {
+                *self = self.$method(rhs.into());
+            }
+        }
+    };
 }
 
+set_operator!(BitOr, bitor, BitOrAssign, bitor_assign, union);
+set_operator!(BitAnd, bitand, BitAndAssign, bitand_assign, intersection);
+set_operator!(Sub, sub, SubAssign, sub_assign, difference);
+set_operator!(
+    BitXor,
+    bitxor,
+    BitXorAssign,
+    bitxor_assign,
+    symmetric_difference
+);
+
 impl IntoIterator for SignalSet {
     type Item = Signal;
     type IntoIter = SignalSetIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        SignalSetIter(self.0.get())
+        SignalSetIter(self.0)
     }
 }
 
@@ -287,7 +842,7 @@ impl fmt::Debug for SignalSet {
 /// # Notes
 ///
 /// The order in which the signals are iterated over is undefined.
-pub struct SignalSetIter(u8);
+pub struct SignalSetIter(u128);
 
 impl Iterator for SignalSetIter {
     type Item = Signal;
@@ -300,12 +855,17 @@ impl Iterator for SignalSetIter {
             2 => Some(Signal::Terminate),
             3 => Some(Signal::User1),
             4 => Some(Signal::User2),
+            5 => Some(Signal::Hangup),
+            6 => Some(Signal::Child),
+            7 => Some(Signal::WindowChange),
+            8 => Some(Signal::Pipe),
+            n @ REALTIME_SHIFT..OTHER_SHIFT => Some(Signal::Realtime((n - REALTIME_SHIFT) as u8)),
+            n @ OTHER_SHIFT..128 => Some(Signal::Other((n - OTHER_SHIFT + 1) as i32)),
             _ => None,
         }
-        .map(|signal| {
+        .inspect(|_| {
             // Remove the signal from the set.
             self.0 &= !(1 << n);
-            signal
         })
     }
 
@@ -345,7 +905,13 @@ impl fmt::Debug for SignalSetIter {
 }
 
 /// Process signal returned by [`Signals`].
+///
+/// This only includes signals that can be caught, see [`SendableSignal`] to
+/// send signals that can't be caught, such as `SIGKILL`.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[non_exhaustive]
+// `Signal` is still small enough to be cheap to copy.
+#[allow(variant_size_differences)]
 pub enum Signal {
     /// Interrupt signal.
     ///
@@ -379,52 +945,865 @@ pub enum Signal {
     ///
     /// Corresponds to POSIX signal `SIGUSR2`.
     User2,
+    /// Hangup signal.
+    ///
+    /// This signal is received when the process's controlling terminal is
+    /// closed. Daemons, which don't have a controlling terminal, by convention
+    /// use this signal as a request to reload their configuration.
+    ///
+    /// Corresponds to POSIX signal `SIGHUP`.
+    Hangup,
+    /// Child process stopped, continued or terminated.
+    ///
+    /// This signal is received when one of the child processes of the process
+    /// stops, continues or terminates. See [`SignalInfo::child`] for the
+    /// status of the child process.
+    ///
+    /// Note that multiple instances of this signal can be merged into a
+    /// single one if they are not received quickly enough. So when receiving
+    /// this signal it's best to use [`reap_children`] to collect the exit
+    /// status of *all* terminated child processes.
+    ///
+    /// This signal is not part of [`SignalSet::all`].
+    ///
+    /// Corresponds to POSIX signal `SIGCHLD`.
+    Child,
+    /// Terminal window size changed.
+    ///
+    /// This signal is received when the size of the controlling terminal
+    /// changes. See [`SignalInfo::window_size`] for the new size.
+    ///
+    /// This signal is not part of [`SignalSet::all`].
+    ///
+    /// Corresponds to signal `SIGWINCH`.
+    WindowChange,
+    /// Broken pipe.
+    ///
+    /// This signal is received when writing to a pipe or socket which read
+    /// side is closed.
+    ///
+    /// The Rust runtime ignores this signal by default, see [`PipePolicy`] to
+    /// change that.
+    ///
+    /// This signal is not part of [`SignalSet::all`].
+    ///
+    /// Corresponds to POSIX signal `SIGPIPE`.
+    Pipe,
+    /// Real-time signal.
+    ///
+    /// Real-time signals have no predefined meaning, their use is defined by
+    /// the application. Unlike the other signals multiple instances of the
+    /// same real-time signal are queued, rather than merged into one, and are
+    /// all received one by one.
+    ///
+    /// The value is the offset from `SIGRTMIN`, which is determined at
+    /// runtime, e.g. `Realtime(1)` corresponds to `SIGRTMIN+1`. The offset
+    /// may not be larger than [`Signal::MAX_REALTIME`], however the actual
+    /// number of real-time signals available may be lower (`SIGRTMAX -
+    /// SIGRTMIN`). Using an offset outside of that range will return an error
+    /// when creating [`Signals`] or sending the signal.
+    ///
+    /// This signal is not part of [`SignalSet::all`].
+    ///
+    /// # Notes
+    ///
+    /// This is only supported on Android and Linux.
+    ///
+    /// Corresponds to POSIX signals `SIGRTMIN` to `SIGRTMAX`.
+    Realtime(u8),
+    /// Any other signal, using the platform specific signal number.
+    ///
+    /// This can be used to receive and send signals that don't have their own
+    /// variant (yet). Signals that do have their own variant are never
+    /// returned as `Other`, e.g. [`Signals`] returns [`Signal::Interrupt`]
+    /// rather than `Other(SIGINT)`.
+    ///
+    /// The signal number must be within `1..=`[`Signal::MAX_OTHER`] to be
+    /// stored in a [`SignalSet`]. Signals that can't be caught, i.e. `SIGKILL`
+    /// and `SIGSTOP`, are invalid, see [`SendableSignal`] to send them.
+    ///
+    /// This signal is not part of [`SignalSet::all`].
+    Other(i32),
 }
 
-impl BitOr for Signal {
-    type Output = SignalSet;
+impl Signal {
+    /// Maximum offset of [`Signal::Realtime`] that can be stored in a
+    /// [`SignalSet`].
+    pub const MAX_REALTIME: u8 = 31;
 
-    fn bitor(self, rhs: Self) -> SignalSet {
-        Into::<SignalSet>::into(self) | rhs
+    /// Maximum signal number of [`Signal::Other`] that can be stored in a
+    /// [`SignalSet`].
+    pub const MAX_OTHER: i32 = 64;
+
+    /// Returns the platform specific number of the signal.
+    ///
+    /// Returns `None` if the signal is not supported on this platform, e.g. a
+    /// [`Signal::Realtime`] signal outside of the `SIGRTMIN..=SIGRTMAX` range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio_signals::Signal;
+    ///
+    /// assert_eq!(Signal::Interrupt.number(), Some(2));
+    /// assert_eq!(Signal::Terminate.number(), Some(15));
+    /// ```
+    pub fn number(self) -> Option<i32> {
+        let raw_signal = sys::raw_signal(self);
+        sys::is_valid_signal(raw_signal).then_some(raw_signal)
     }
-}
 
-impl BitOr<SignalSet> for Signal {
-    type Output = SignalSet;
+    /// Create a signal from the platform specific signal `number`.
+    ///
+    /// Returns `None` if `number` is not a valid signal number on this
+    /// platform, or if the signal can't be caught (e.g. `SIGKILL`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio_signals::Signal;
+    ///
+    /// assert_eq!(Signal::from_number(2), Some(Signal::Interrupt));
+    /// assert_eq!(Signal::from_number(15), Some(Signal::Terminate));
+    /// assert_eq!(Signal::from_number(0), None);
+    /// assert_eq!(Signal::from_number(libc::SIGKILL), None);
+    /// ```
+    pub fn from_number(number: i32) -> Option<Signal> {
+        (sys::is_valid_signal(number) && !sys::is_uncatchable(number))
+            .then(|| sys::from_raw_signal(number))
+    }
 
-    fn bitor(self, rhs: SignalSet) -> SignalSet {
-        rhs | self
+    /// Returns all signals supported on this platform, ordered by their signal
+    /// number.
+    ///
+    /// # Examples
+    ///
+    /// Print a table of all signals, like `kill -l`.
+    ///
+    /// ```
+    /// use mio_signals::Signal;
+    ///
+    /// for signal in Signal::supported() {
+    ///     println!("{:>2}) {}", signal.number().unwrap(), signal);
+    /// }
+    /// ```
+    pub fn supported() -> impl Iterator<Item = Signal> {
+        (1..=sys::MAX_RAW_SIGNAL).filter_map(Signal::from_number)
     }
 }
 
-/// Send `signal` to the process with `pid`.
+/// Signal that can be send, see [`send_signal`].
 ///
-/// # Examples
+/// Next to all signals in [`Signal`] this includes the signals that can't be
+/// caught, and thus can't be received using [`Signals`], such as `SIGKILL`.
+/// All sending functions accept anything that can be converted into a
+/// `SendableSignal`, so a [`Signal`] can be used directly.
 ///
-/// Send ourselves a signal.
+/// # Examples
 ///
 /// ```
-/// use std::{io, process};
+/// # fn main() -> std::io::Result<()> {
+/// use std::os::unix::process::ExitStatusExt;
+/// use std::process::Command;
 ///
-/// use mio::{Poll, Events, Interest, Token};
-/// use mio_signals::{Signals, Signal, SignalSet, send_signal};
-///
-/// const SIGNAL: Token = Token(10);
+/// use mio_signals::{SendableSignal, send_signal};
 ///
-/// fn main() -> io::Result<()> {
-///     let mut poll = Poll::new()?;
-///     let mut events = Events::with_capacity(8);
+/// let mut child = Command::new("sleep").arg("10").spawn()?;
+/// send_signal(child.id(), SendableSignal::Kill)?;
 ///
-///     // Create a `Signals` instance that will catch signals for us.
-///     let mut signals = Signals::new(SignalSet::all())?;
-///     // And register it with our `Poll` instance.
-///     poll.registry().register(&mut signals, SIGNAL, Interest::READABLE)?;
+/// let status = child.wait()?;
+/// assert_eq!(status.signal(), Some(libc::SIGKILL));
+/// # Ok(())
+/// # }
+/// ```
 ///
-///     // Send ourselves a signal.
-///     send_signal(process::id(), Signal::Interrupt)?;
+/// Signals that can't be caught can't be used to create [`Signals`].
 ///
-///     loop {
-///         poll.poll(&mut events, None)?;
+/// ```compile_fail
+/// use mio_signals::{SendableSignal, Signals};
+///
+/// let signals = Signals::new(SendableSignal::Kill.into());
+/// ```
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub enum SendableSignal {
+    /// A signal that can be caught.
+    ///
+    /// Converting a [`Signal`] into a `SendableSignal` never returns
+    /// `Signal(Other(SIGCONT))`, it returns [`SendableSignal::Continue`]
+    /// instead. When created directly the signal is normalised when
+    /// comparing and hashing, e.g. `Signal(Other(SIGCONT))` is equal to
+    /// `Continue` and `Signal(Other(SIGINT))` to `Signal(Interrupt)`.
+    ///
+    /// `Signal(Other(SIGKILL))` and `Signal(Other(SIGSTOP))` are not
+    /// rejected when created, but sending them returns
+    /// [`SendError::InvalidSignal`], use [`SendableSignal::Kill`] and
+    /// [`SendableSignal::Stop`] instead.
+    Signal(Signal),
+    /// Kill signal.
+    ///
+    /// Immediately terminates the process, this signal can't be caught,
+    /// blocked or ignored.
+    ///
+    /// Corresponds to POSIX signal `SIGKILL`.
+    Kill,
+    /// Stop signal.
+    ///
+    /// Stops the process until it receives [`SendableSignal::Continue`], this
+    /// signal can't be caught, blocked or ignored.
+    ///
+    /// Corresponds to POSIX signal `SIGSTOP`.
+    Stop,
+    /// Continue signal.
+    ///
+    /// Continues a stopped process. Unlike `SIGKILL` and `SIGSTOP` this signal
+    /// can be caught, as `Signal::Other(SIGCONT)`, but the process is always
+    /// continued.
+    ///
+    /// Corresponds to POSIX signal `SIGCONT`.
+    Continue,
+}
+
+impl SendableSignal {
+    /// Returns the platform specific number of the signal.
+    ///
+    /// Returns `None` if the signal is not supported on this platform, see
+    /// [`Signal::number`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio_signals::{SendableSignal, Signal};
+    ///
+    /// assert_eq!(SendableSignal::Kill.number(), Some(9));
+    /// assert_eq!(SendableSignal::from(Signal::Terminate).number(), Some(15));
+    /// ```
+    pub fn number(self) -> Option<i32> {
+        let raw_signal = sys::raw_sendable_signal(self);
+        sys::is_valid_signal(raw_signal).then_some(raw_signal)
+    }
+
+    /// Create a signal from the platform specific signal `number`.
+    ///
+    /// Returns `None` if `number` is not a valid signal number on this
+    /// platform.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio_signals::{SendableSignal, Signal};
+    ///
+    /// assert_eq!(SendableSignal::from_number(9), Some(SendableSignal::Kill));
+    /// assert_eq!(SendableSignal::from_number(15), Some(Signal::Terminate.into()));
+    /// assert_eq!(SendableSignal::from_number(0), None);
+    /// ```
+    pub fn from_number(number: i32) -> Option<SendableSignal> {
+        match number {
+            libc::SIGKILL => Some(SendableSignal::Kill),
+            libc::SIGSTOP => Some(SendableSignal::Stop),
+            number => Signal::from_number(number).map(SendableSignal::from),
+        }
+    }
+
+    /// Returns the signal as [`Signal`], if it can be caught.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mio_signals::{SendableSignal, Signal};
+    ///
+    /// assert_eq!(SendableSignal::Kill.signal(), None);
+    /// assert_eq!(SendableSignal::from(Signal::User1).signal(), Some(Signal::User1));
+    /// ```
+    pub const fn signal(self) -> Option<Signal> {
+        match self {
+            SendableSignal::Signal(signal) => Some(signal),
+            SendableSignal::Continue => Some(Signal::Other(libc::SIGCONT)),
+            SendableSignal::Kill | SendableSignal::Stop => None,
+        }
+    }
+
+    /// Returns the normalised form of `self`, i.e. the value returned by
+    /// [`SendableSignal::from_number`] for the signal's number.
+    fn normalise(self) -> SendableSignal {
+        match self {
+            // NOTE: `Other(SIGKILL)` and `Other(SIGSTOP)` can't be sent, so
+            // they're not the same as `Kill` and `Stop`.
+            SendableSignal::Signal(Signal::Other(number)) if !sys::is_uncatchable(number) => {
+                SendableSignal::from_number(number).unwrap_or(self)
+            }
+            signal => signal,
+        }
+    }
+
+    /// Key used to compare and hash the normalised signal.
+    fn key(self) -> (u8, Option<Signal>) {
+        match self.normalise() {
+            SendableSignal::Signal(signal) => (0, Some(signal)),
+            SendableSignal::Kill => (1, None),
+            SendableSignal::Stop => (2, None),
+            SendableSignal::Continue => (3, None),
+        }
+    }
+}
+
+impl PartialEq for SendableSignal {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for SendableSignal {}
+
+impl PartialOrd for SendableSignal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SendableSignal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl Hash for SendableSignal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl From<Signal> for SendableSignal {
+    fn from(signal: Signal) -> SendableSignal {
+        match signal {
+            Signal::Other(libc::SIGCONT) => SendableSignal::Continue,
+            signal => SendableSignal::Signal(signal),
+        }
+    }
+}
+
+impl BitOr for Signal {
+    type Output = SignalSet;
+
+    fn bitor(self, rhs: Self) -> SignalSet {
+        Into::<SignalSet>::into(self) | rhs
+    }
+}
+
+impl BitOr<SignalSet> for Signal {
+    type Output = SignalSet;
+
+    fn bitor(self, rhs: SignalSet) -> SignalSet {
+        rhs | self
+    }
+}
+
+/// What to do with [`Signal::Pipe`] (`SIGPIPE`), see
+/// [`SignalsBuilder::pipe_policy`].
+///
+/// Before calling `main` the Rust runtime sets the action of `SIGPIPE` to
+/// ignore the signal. This means that writing to a closed pipe returns an
+/// error (`EPIPE`), rather than terminating the process.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum PipePolicy {
+    /// Receive the signal as [`Signal::Pipe`] using [`Signals`].
+    Receive,
+    /// Restore the default action, which terminates the process.
+    Default,
+    /// Keep ignoring the signal.
+    Ignore,
+}
+
+/// Implementation used by [`Signals`] to receive signals, see
+/// [`SignalsBuilder::backend`].
+///
+/// The default is [`Backend::Auto`].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Backend {
+    /// Block the signals and receive them using [`signalfd(2)`].
+    ///
+    /// This relies on the signals being blocked in all threads, which is only
+    /// the case for threads spawned after `Signals` is created, see
+    /// [Multithreaded process]. Only supported on Android and Linux, on other
+    /// platforms this returns an error of kind [`io::ErrorKind::Unsupported`].
+    ///
+    /// [`signalfd(2)`]: https://man7.org/linux/man-pages/man2/signalfd.2.html
+    /// [Multithreaded process]: Signals#multithreaded-process
+    SignalFd,
+    /// Install a signal handler, using [`sigaction(2)`] with `SA_SIGINFO`,
+    /// that writes the signal to a pipe.
+    ///
+    /// This works no matter how many threads exist, but only a single
+    /// `Signals` instance can receive a particular signal. If a signal handler
+    /// was already installed for a signal it's called after writing to the
+    /// pipe, and it's restored when `Signals` is dropped.
+    ///
+    /// The sender of the signal is only known on Android and Linux. If too
+    /// many signals are received at once they can be dropped, which only
+    /// matters for real-time signals, as other signals are merged anyway.
+    ///
+    /// [`sigaction(2)`]: https://man7.org/linux/man-pages/man2/sigaction.2.html
+    SelfPipe,
+    /// Pick the best option for the platform.
+    ///
+    /// On Android and Linux this uses [`Backend::SignalFd`] if no other
+    /// threads exist yet, and [`Backend::SelfPipe`] otherwise. On platforms
+    /// that support [`kqueue(2)`] this uses the `EVFILT_SIGNAL` event filter,
+    /// which doesn't depend on threads.
+    ///
+    /// [`kqueue(2)`]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
+    #[default]
+    Auto,
+}
+
+/// Information about a received signal, returned by
+/// [`Signals::receive_info`].
+///
+/// # Notes
+///
+/// Not all information is available on all platforms. On platforms that
+/// support [`kqueue(2)`] the sender of the signal is unknown, meaning that
+/// [`pid`] and [`uid`] return `None`, [`origin`] returns
+/// [`SignalOrigin::Unknown`] and [`value`] returns `None`. The same is true
+/// for [`Backend::SelfPipe`] on those platforms, and on all platforms it
+/// doesn't provide the exit status of child processes, i.e. [`child`] returns
+/// `None`.
+///
+/// [`kqueue(2)`]: https://www.freebsd.org/cgi/man.cgi?query=kqueue&sektion=2
+/// [`child`]: SignalInfo::child
+/// [`pid`]: SignalInfo::pid
+/// [`uid`]: SignalInfo::uid
+/// [`origin`]: SignalInfo::origin
+/// [`value`]: SignalInfo::value
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SignalInfo {
+    signal: Signal,
+    origin: SignalOrigin,
+    pid: Option<u32>,
+    uid: Option<u32>,
+    value: Option<SignalValue>,
+    child: Option<ChildExit>,
+    window_size: Option<WindowSize>,
+}
+
+impl SignalInfo {
+    /// Create a new `SignalInfo` without any additional information.
+    const fn new(signal: Signal) -> SignalInfo {
+        SignalInfo {
+            signal,
+            origin: SignalOrigin::Unknown,
+            pid: None,
+            uid: None,
+            value: None,
+            child: None,
+            window_size: None,
+        }
+    }
+
+    /// Add the information that isn't provided by the OS.
+    fn complete(mut self) -> SignalInfo {
+        if let Signal::WindowChange = self.signal {
+            self.window_size = window_size().ok();
+        }
+        self
+    }
+
+    /// The received signal.
+    pub const fn signal(&self) -> Signal {
+        self.signal
+    }
+
+    /// Where the signal originated from.
+    pub const fn origin(&self) -> SignalOrigin {
+        self.origin
+    }
+
+    /// Process id of the process that sent the signal.
+    ///
+    /// This is only set if the signal was send by a process, see
+    /// [`SignalOrigin::is_process`], or for [`Signal::Child`] in which case
+    /// it's the process id of the child process.
+    pub const fn pid(&self) -> Option<u32> {
+        self.pid
+    }
+
+    /// Real user id of the process that sent the signal.
+    ///
+    /// This is set in the same cases as [`SignalInfo::pid`].
+    pub const fn uid(&self) -> Option<u32> {
+        self.uid
+    }
+
+    /// The value send along with the signal, only set for signals send using
+    /// [`sigqueue(3)`] ([`SignalOrigin::Queue`]) or by an expired timer
+    /// ([`SignalOrigin::Timer`]).
+    ///
+    /// [`sigqueue(3)`]: https://man7.org/linux/man-pages/man3/sigqueue.3.html
+    pub const fn value(&self) -> Option<SignalValue> {
+        self.value
+    }
+
+    /// The status of the child process that caused the signal, only set for
+    /// [`Signal::Child`].
+    ///
+    /// # Notes
+    ///
+    /// This is only supported on Android and Linux, on other platforms this
+    /// always returns `None`. Use [`reap_children`] instead.
+    ///
+    /// This doesn't reap the child process, it will remain a zombie process
+    /// until it's waited on, e.g. using [`reap_children`].
+    pub const fn child(&self) -> Option<ChildExit> {
+        self.child
+    }
+
+    /// The size of the controlling terminal, only set for
+    /// [`Signal::WindowChange`].
+    ///
+    /// This is retrieved using [`window_size`] when the signal is received,
+    /// if that fails this returns `None`.
+    pub const fn window_size(&self) -> Option<WindowSize> {
+        self.window_size
+    }
+}
+
+/// Creates a placeholder `SignalInfo`, used to create the buffer for
+/// [`Signals::receive_many`].
+///
+/// The signal of the returned value is `Signal::Other(0)`, which isn't a valid
+/// signal, without any additional information.
+impl Default for SignalInfo {
+    fn default() -> SignalInfo {
+        SignalInfo::new(Signal::Other(0))
+    }
+}
+
+/// Origin of a signal, see [`SignalInfo::origin`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum SignalOrigin {
+    /// Send by a process using [`kill(2)`], e.g. using [`send_signal`].
+    ///
+    /// [`kill(2)`]: https://man7.org/linux/man-pages/man2/kill.2.html
+    Kill,
+    /// Send by a process using [`sigqueue(3)`], possibly with a value (see
+    /// [`SignalInfo::value`]).
+    ///
+    /// [`sigqueue(3)`]: https://man7.org/linux/man-pages/man3/sigqueue.3.html
+    Queue,
+    /// Send by a process to a specific thread, e.g. using [`tgkill(2)`] or
+    /// [`raise(3)`].
+    ///
+    /// [`tgkill(2)`]: https://man7.org/linux/man-pages/man2/tgkill.2.html
+    /// [`raise(3)`]: https://man7.org/linux/man-pages/man3/raise.3.html
+    Thread,
+    /// Send by the kernel, e.g. [`Signal::Child`] when a child process
+    /// terminates.
+    Kernel,
+    /// Send by the terminal driver, e.g. [`Signal::Interrupt`] when pressing
+    /// ctrl-c or [`Signal::WindowChange`] when the window is resized.
+    ///
+    /// Linux doesn't record this separately, this is used for the signals
+    /// the terminal driver generates if they are send by the kernel.
+    Tty,
+    /// Send by an expired POSIX timer, see [`timer_create(2)`].
+    ///
+    /// [`timer_create(2)`]: https://man7.org/linux/man-pages/man2/timer_create.2.html
+    Timer,
+    /// The origin is unknown, either because the platform doesn't provide it
+    /// or because it's not one of the above.
+    Unknown,
+}
+
+impl SignalOrigin {
+    /// Returns `true` if the signal was send by a process, i.e. it's
+    /// [`SignalOrigin::Kill`], [`SignalOrigin::Queue`] or
+    /// [`SignalOrigin::Thread`].
+    pub const fn is_process(self) -> bool {
+        matches!(
+            self,
+            SignalOrigin::Kill | SignalOrigin::Queue | SignalOrigin::Thread
+        )
+    }
+}
+
+/// Value send along with a signal, see [`SignalInfo::value`].
+///
+/// This mirrors `union sigval`, which holds either an integer or a pointer.
+/// Which one is used is up to the sender and receiver to agree on.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SignalValue(usize);
+
+impl SignalValue {
+    /// Create a new value from an integer (`sival_int`).
+    pub fn from_int(int: i32) -> SignalValue {
+        let mut value = libc::sigval {
+            sival_ptr: ptr::null_mut(),
+        };
+        // SAFETY: see `as_int`.
+        unsafe { ptr::addr_of_mut!(value).cast::<libc::c_int>().write(int) };
+        SignalValue(value.sival_ptr as usize)
+    }
+
+    /// Create a new value from a pointer (`sival_ptr`).
+    ///
+    /// Note that the pointer can't be dereferenced by the receiving process,
+    /// unless both processes share (part of) their address space.
+    pub fn from_ptr(ptr: *mut libc::c_void) -> SignalValue {
+        SignalValue(ptr as usize)
+    }
+
+    /// The value as integer (`sival_int`).
+    pub fn as_int(self) -> i32 {
+        let value = libc::sigval {
+            sival_ptr: self.as_ptr(),
+        };
+        // SAFETY: `sigval` is a union of an `int` and a pointer, starting at
+        // the same address.
+        unsafe { ptr::addr_of!(value).cast::<libc::c_int>().read() }
+    }
+
+    /// The value as pointer (`sival_ptr`).
+    ///
+    /// Note that the pointer is only valid in the address space of the
+    /// sending process.
+    pub const fn as_ptr(self) -> *mut libc::c_void {
+        self.0 as *mut libc::c_void
+    }
+}
+
+/// Number of times each signal was received, returned by [`Signals::drain`].
+#[derive(Clone, Eq, PartialEq)]
+pub struct SignalCounts {
+    signals: SignalSet,
+    /// Count per signal, indexed by the bit of the signal in `SignalSet`.
+    counts: [u32; u128::BITS as usize],
+}
+
+impl SignalCounts {
+    /// Create an empty `SignalCounts`.
+    const fn new() -> SignalCounts {
+        SignalCounts {
+            signals: SignalSet::empty(),
+            counts: [0; u128::BITS as usize],
+        }
+    }
+
+    /// Add `n` to the count of `signal`.
+    fn add(&mut self, signal: Signal, n: u32) {
+        let set = SignalSet::from(signal);
+        let index = set.0.trailing_zeros() as usize;
+        self.counts[index] = self.counts[index].saturating_add(n);
+        self.signals |= set;
+    }
+
+    /// All signals that were received at least once.
+    pub const fn signals(&self) -> SignalSet {
+        self.signals
+    }
+
+    /// Number of times `signal` was received.
+    ///
+    /// # Notes
+    ///
+    /// On platforms that use `signalfd(2)` (Android and Linux) multiple
+    /// instances of a (non real-time) signal send before it's received are
+    /// merged into one, so they are counted once.
+    pub fn count(&self, signal: Signal) -> u32 {
+        match SignalSet::from_signal(signal) {
+            Some(set) => self.counts[set.0.trailing_zeros() as usize],
+            None => 0,
+        }
+    }
+
+    /// Total number of signals received.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().map(|n| u64::from(*n)).sum()
+    }
+
+    /// Returns `true` if no signals were received.
+    pub const fn is_empty(&self) -> bool {
+        self.signals.is_empty()
+    }
+
+    /// Iterate over all received signals and the number of times they were
+    /// received.
+    pub fn iter(&self) -> impl Iterator<Item = (Signal, u32)> + '_ {
+        self.signals
+            .into_iter()
+            .map(|signal| (signal, self.count(signal)))
+    }
+}
+
+impl fmt::Debug for SignalCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Status of a child process, see [`SignalInfo::child`] and
+/// [`reap_children`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ChildExit {
+    pid: u32,
+    status: ExitStatus,
+}
+
+impl ChildExit {
+    /// Process id of the child process.
+    pub const fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Status of the child process.
+    ///
+    /// See [`ExitStatusExt`] to determine if the process was stopped or
+    /// continued, rather than terminated.
+    ///
+    /// [`ExitStatusExt`]: std::os::unix::process::ExitStatusExt
+    pub const fn status(&self) -> ExitStatus {
+        self.status
+    }
+
+    /// Exit code of the child process, if it exited normally.
+    pub fn code(&self) -> Option<i32> {
+        self.status.code()
+    }
+
+    /// Raw signal number that terminated the child process, if it was
+    /// terminated by a signal.
+    pub fn signal(&self) -> Option<i32> {
+        std::os::unix::process::ExitStatusExt::signal(&self.status)
+    }
+
+    /// Whether or not the child process dumped core when it was terminated by
+    /// a signal.
+    pub fn core_dumped(&self) -> bool {
+        std::os::unix::process::ExitStatusExt::core_dumped(&self.status)
+    }
+}
+
+/// Size of a terminal window, see [`window_size`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct WindowSize {
+    rows: u16,
+    columns: u16,
+    width: u16,
+    height: u16,
+}
+
+impl WindowSize {
+    /// Number of rows, in characters.
+    pub const fn rows(&self) -> u16 {
+        self.rows
+    }
+
+    /// Number of columns, in characters.
+    pub const fn columns(&self) -> u16 {
+        self.columns
+    }
+
+    /// Width in pixels, zero if unknown.
+    pub const fn pixel_width(&self) -> u16 {
+        self.width
+    }
+
+    /// Height in pixels, zero if unknown.
+    pub const fn pixel_height(&self) -> u16 {
+        self.height
+    }
+}
+
+/// Get the size of the controlling terminal.
+///
+/// This uses the `TIOCGWINSZ` [`ioctl(2)`] on `/dev/tty`, which fails if the
+/// process doesn't have a controlling terminal.
+///
+/// [`ioctl(2)`]: https://man7.org/linux/man-pages/man2/ioctl_tty.2.html
+pub fn window_size() -> io::Result<WindowSize> {
+    sys::window_size()
+}
+
+/// Reap all terminated child processes.
+///
+/// Returns an iterator that waits (using [`waitpid(2)`] with `WNOHANG`) on
+/// every child process that has terminated, without blocking. This should be
+/// called after receiving [`Signal::Child`], as multiple instances of this
+/// signal can be merged into one.
+///
+/// # Notes
+///
+/// This reaps **all** terminated child processes, including the ones started
+/// using [`std::process::Command`]. Calling [`Child::wait`] for a reaped
+/// process will return an error.
+///
+/// [`waitpid(2)`]: https://man7.org/linux/man-pages/man2/waitpid.2.html
+/// [`Child::wait`]: std::process::Child::wait
+///
+/// # Examples
+///
+/// ```
+/// use std::process::Command;
+///
+/// use mio_signals::reap_children;
+///
+/// let child = Command::new("true").spawn().unwrap();
+/// # std::thread::sleep(std::time::Duration::from_millis(100));
+///
+/// for exit in reap_children() {
+///     println!("child process {} exited: {}", exit.pid(), exit.status());
+///     # assert_eq!(exit.pid(), child.id());
+///     # assert_eq!(exit.code(), Some(0));
+/// }
+/// ```
+pub fn reap_children() -> ReapChildren {
+    ReapChildren { _priv: () }
+}
+
+/// Iterator returned by [`reap_children`].
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ReapChildren {
+    _priv: (),
+}
+
+impl Iterator for ReapChildren {
+    type Item = ChildExit;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        sys::reap_child()
+    }
+}
+
+/// Send `signal` to the process with `pid`.
+///
+/// # Examples
+///
+/// Send ourselves a signal.
+///
+/// ```
+/// use std::{io, process};
+///
+/// use mio::{Poll, Events, Interest, Token};
+/// use mio_signals::{Signals, Signal, SignalSet, send_signal};
+///
+/// const SIGNAL: Token = Token(10);
+///
+/// fn main() -> io::Result<()> {
+///     let mut poll = Poll::new()?;
+///     let mut events = Events::with_capacity(8);
+///
+///     // Create a `Signals` instance that will catch signals for us.
+///     let mut signals = Signals::new(SignalSet::all())?;
+///     // And register it with our `Poll` instance.
+///     poll.registry().register(&mut signals, SIGNAL, Interest::READABLE)?;
+///
+///     // Send ourselves a signal.
+///     send_signal(process::id(), Signal::Interrupt)?;
+///
+///     loop {
+///         poll.poll(&mut events, None)?;
 ///
 ///         for event in events.iter() {
 ///             match event.token() {
@@ -449,6 +1828,287 @@ impl BitOr<SignalSet> for Signal {
 ///     }
 /// }
 /// ```
-pub fn send_signal(pid: u32, signal: Signal) -> io::Result<()> {
-    sys::send_signal(pid, signal)
+pub fn send_signal<S>(pid: u32, signal: S) -> Result<(), SendError>
+where
+    S: Into<SendableSignal>,
+{
+    send_signal_to(Target::Process(pid), signal)
+}
+
+/// Target of [`send_signal_to`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Target {
+    /// The process with the process id.
+    Process(u32),
+    /// All processes in the process group with the process group id.
+    Group(u32),
+    /// All processes in the process group of the calling process.
+    OwnGroup,
+    /// All processes the calling process has permission to send signals to,
+    /// except for system processes (such as init).
+    All,
+}
+
+impl Target {
+    /// Returns the `pid` argument for `kill(2)`.
+    fn kill_pid(self) -> Result<libc::pid_t, SendError> {
+        match self {
+            // NOTE: zero and negative values have a special meaning.
+            Target::Process(pid) => positive_pid(pid),
+            // NOTE: -1 means all processes, so process group 1 can't be used.
+            Target::Group(pgid) => match positive_pid(pgid) {
+                Ok(pgid) if pgid > 1 => Ok(-pgid),
+                _ => Err(SendError::InvalidTarget),
+            },
+            Target::OwnGroup => Ok(0),
+            Target::All => Ok(-1),
+        }
+    }
+}
+
+/// Send `signal` to `target`.
+///
+/// This uses [`kill(2)`], see [`Target`] for the possible targets.
+///
+/// Returns [`SendError::InvalidTarget`] if the process (group) id is invalid,
+/// e.g. zero.
+///
+/// [`kill(2)`]: https://man7.org/linux/man-pages/man2/kill.2.html
+///
+/// # Examples
+///
+/// Stop a pipeline of processes, started in its own process group.
+///
+/// ```
+/// # fn main() -> std::io::Result<()> {
+/// use std::os::unix::process::{CommandExt, ExitStatusExt};
+/// use std::process::Command;
+///
+/// use mio_signals::{Signal, Target, send_signal_to};
+///
+/// let mut child = Command::new("sh")
+///     .args(["-c", "sleep 10 | sleep 10"])
+///     // Start the process in a new process group.
+///     .process_group(0)
+///     .spawn()?;
+///
+/// // The process group id is the same as the process id of its leader.
+/// send_signal_to(Target::Group(child.id()), Signal::Terminate)?;
+///
+/// let status = child.wait()?;
+/// assert_eq!(status.signal(), Some(libc::SIGTERM));
+/// # Ok(())
+/// # }
+/// ```
+pub fn send_signal_to<S>(target: Target, signal: S) -> Result<(), SendError>
+where
+    S: Into<SendableSignal>,
+{
+    sys::send_signal(target.kill_pid()?, signal.into()).map_err(SendError::from)
+}
+
+/// Convert `pid` into a `pid_t`, returning an error if it's not positive.
+fn positive_pid(pid: u32) -> Result<libc::pid_t, SendError> {
+    match libc::pid_t::try_from(pid) {
+        Ok(pid) if pid > 0 => Ok(pid),
+        _ => Err(SendError::InvalidTarget),
+    }
+}
+
+/// Returns `true` if the process with `pid` exists.
+///
+/// This uses [`kill(2)`] to send the null signal (0) to the process, which
+/// only checks if the process exists and if we're allowed to send it signals.
+/// Processes we're not allowed to send signals to are reported as existing,
+/// see [`can_signal`] to check that.
+///
+/// Note that a zombie process, i.e. a terminated process that wasn't reaped
+/// yet, still exists.
+///
+/// [`kill(2)`]: https://man7.org/linux/man-pages/man2/kill.2.html
+///
+/// # Examples
+///
+/// ```
+/// use std::process;
+///
+/// use mio_signals::process_exists;
+///
+/// assert!(process_exists(process::id())?);
+/// # Ok::<(), mio_signals::SendError>(())
+/// ```
+pub fn process_exists(pid: u32) -> Result<bool, SendError> {
+    match probe_process(pid) {
+        Ok(()) | Err(SendError::PermissionDenied) => Ok(true),
+        Err(SendError::NoSuchProcess) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Returns `true` if we're allowed to send signals to the process with `pid`.
+///
+/// This returns `false` if the process doesn't exist, use [`process_exists`]
+/// to tell a non-existing process apart from a process we're not allowed to
+/// signal.
+pub fn can_signal(pid: u32) -> Result<bool, SendError> {
+    match probe_process(pid) {
+        Ok(()) => Ok(true),
+        Err(SendError::PermissionDenied | SendError::NoSuchProcess) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Send the null signal to the process with `pid`.
+fn probe_process(pid: u32) -> Result<(), SendError> {
+    sys::probe_process(positive_pid(pid)?).map_err(SendError::from)
+}
+
+/// Send `signal` to the thread with `tid`, in the calling process.
+///
+/// This uses [`tgkill(2)`], use [`thread_id`] to get the thread id of a
+/// thread. See [`send_signal_to_pthread`] for a portable alternative.
+///
+/// # Notes
+///
+/// The signal is only delivered to the thread with `tid`. If the signal is
+/// part of a [`Signals`] instance the thread will have the signal blocked (if
+/// the thread was spawned after `Signals` was created). On Android and Linux
+/// this means that the signal can only be received by calling one of the
+/// receive methods on that thread, as [`signalfd(2)`] only returns signals
+/// pending for the process or the calling thread.
+///
+/// This is only supported on Android and Linux.
+///
+/// [`tgkill(2)`]: https://man7.org/linux/man-pages/man2/tgkill.2.html
+/// [`signalfd(2)`]: https://man7.org/linux/man-pages/man2/signalfd.2.html
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn send_signal_to_thread<S>(tid: u32, signal: S) -> Result<(), SendError>
+where
+    S: Into<SendableSignal>,
+{
+    sys::send_signal_to_thread(positive_pid(tid)?, signal.into()).map_err(SendError::from)
+}
+
+/// Returns the thread id of the calling thread, see
+/// [`send_signal_to_thread`].
+///
+/// This uses [`gettid(2)`] and is only supported on Android and Linux.
+///
+/// [`gettid(2)`]: https://man7.org/linux/man-pages/man2/gettid.2.html
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn thread_id() -> u32 {
+    sys::thread_id()
+}
+
+/// Send `signal` to the `thread`, in the calling process.
+///
+/// This uses [`pthread_kill(3)`]. See [`send_signal_to_join_handle`] for
+/// threads spawned using [`std::thread`]. See [`send_signal_to_thread`] for a
+/// note about receiving the signal.
+///
+/// [`pthread_kill(3)`]: https://man7.org/linux/man-pages/man3/pthread_kill.3.html
+pub fn send_signal_to_pthread<S>(thread: libc::pthread_t, signal: S) -> Result<(), SendError>
+where
+    S: Into<SendableSignal>,
+{
+    sys::send_signal_to_pthread(thread, signal.into()).map_err(SendError::from)
+}
+
+/// Send `signal` to the thread of `handle`.
+///
+/// See [`send_signal_to_pthread`].
+///
+/// # Examples
+///
+/// Waking up a thread waiting for a signal.
+///
+/// ```
+/// # #[cfg(any(target_os = "linux", target_os = "android"))]
+/// # fn main() -> std::io::Result<()> {
+/// use std::{io, thread};
+///
+/// use mio::{Events, Interest, Poll, Token};
+/// use mio_signals::{Signal, Signals, send_signal_to_join_handle};
+///
+/// // Block `SIGUSR1` in the calling thread and all threads spawned after it.
+/// let mut signals = Signals::new(Signal::User1.into())?;
+///
+/// let handle = thread::spawn(move || -> io::Result<Option<Signal>> {
+///     let mut poll = Poll::new()?;
+///     let mut events = Events::with_capacity(8);
+///     poll.registry().register(&mut signals, Token(0), Interest::READABLE)?;
+///     poll.poll(&mut events, None)?;
+///     signals.receive()
+/// });
+///
+/// send_signal_to_join_handle(&handle, Signal::User1)?;
+/// assert_eq!(handle.join().unwrap()?, Some(Signal::User1));
+/// # Ok(())
+/// # }
+/// # #[cfg(not(any(target_os = "linux", target_os = "android")))]
+/// # fn main() {}
+/// ```
+pub fn send_signal_to_join_handle<T, S>(handle: &JoinHandle<T>, signal: S) -> Result<(), SendError>
+where
+    S: Into<SendableSignal>,
+{
+    send_signal_to_pthread(handle.as_pthread_t() as libc::pthread_t, signal)
+}
+
+/// Send `signal` to the calling thread.
+///
+/// This uses [`raise(3)`]. If `signal` is part of a [`Signals`] instance,
+/// created in this thread (or a thread that spawned this thread), it can be
+/// received using that `Signals` instance in this thread.
+///
+/// [`raise(3)`]: https://man7.org/linux/man-pages/man3/raise.3.html
+pub fn raise<S>(signal: S) -> Result<(), SendError>
+where
+    S: Into<SendableSignal>,
+{
+    sys::raise(signal.into()).map_err(SendError::from)
+}
+
+/// Send `signal`, along with `value`, to the process with `pid`.
+///
+/// This uses [`sigqueue(3)`], the receiving process can retrieve `value` using
+/// [`SignalInfo::value`]. Unlike [`send_signal`] multiple instances of a
+/// real-time signal send this way are queued, rather than merged into one.
+///
+/// [`sigqueue(3)`]: https://man7.org/linux/man-pages/man3/sigqueue.3.html
+///
+/// # Notes
+///
+/// This is only supported on Android, FreeBSD, Linux and NetBSD, on other
+/// platforms this returns [`SendError::Other`] with an error of kind
+/// [`io::ErrorKind::Unsupported`]. Furthermore receiving the value is only
+/// supported on Android and Linux, see [`SignalInfo`].
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(any(target_os = "linux", target_os = "android"))]
+/// # fn main() -> std::io::Result<()> {
+/// use std::process;
+///
+/// use mio_signals::{Signal, SignalValue, Signals, send_signal_with_value};
+///
+/// let mut signals = Signals::new(Signal::User1.into())?;
+///
+/// // Send ourselves a signal with an value.
+/// send_signal_with_value(process::id(), Signal::User1, SignalValue::from_int(7))?;
+///
+/// let info = signals.receive_info()?.unwrap();
+/// assert_eq!(info.signal(), Signal::User1);
+/// assert_eq!(info.value().map(|value| value.as_int()), Some(7));
+/// # Ok(())
+/// # }
+/// # #[cfg(not(any(target_os = "linux", target_os = "android")))]
+/// # fn main() {}
+/// ```
+pub fn send_signal_with_value<S>(pid: u32, signal: S, value: SignalValue) -> Result<(), SendError>
+where
+    S: Into<SendableSignal>,
+{
+    sys::send_signal_with_value(positive_pid(pid)?, signal.into(), value).map_err(SendError::from)
 }
@@ -0,0 +1,303 @@
+//! Parsing and formatting of signals by name.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::sys::{self, OTHER_SIGNALS};
+use crate::{SendableSignal, Signal, SignalSet};
+
+/// Names of the signals with their own variant, without the `SIG` prefix.
+const NAMES: &[(&str, Signal)] = &[
+    ("INT", Signal::Interrupt),
+    ("QUIT", Signal::Quit),
+    ("TERM", Signal::Terminate),
+    ("USR1", Signal::User1),
+    ("USR2", Signal::User2),
+    ("HUP", Signal::Hangup),
+    ("CHLD", Signal::Child),
+    ("WINCH", Signal::WindowChange),
+    ("PIPE", Signal::Pipe),
+];
+
+/// Names of the variants of `Signal`, as used in the `Debug` implementation.
+const VARIANT_NAMES: &[(&str, Signal)] = &[
+    ("Interrupt", Signal::Interrupt),
+    ("Quit", Signal::Quit),
+    ("Terminate", Signal::Terminate),
+    ("User1", Signal::User1),
+    ("User2", Signal::User2),
+    ("Hangup", Signal::Hangup),
+    ("Child", Signal::Child),
+    ("WindowChange", Signal::WindowChange),
+    ("Pipe", Signal::Pipe),
+];
+
+/// Names of the signals in `SendableSignal` that can't be parsed as `Signal`,
+/// without the `SIG` prefix, and the names of their variants.
+const SENDABLE_NAMES: &[(&str, &str, SendableSignal)] = &[
+    ("KILL", "Kill", SendableSignal::Kill),
+    ("STOP", "Stop", SendableSignal::Stop),
+    ("CONT", "Continue", SendableSignal::Continue),
+];
+
+/// Formats the signal using its name, e.g. `SIGINT` for
+/// [`Signal::Interrupt`].
+///
+/// Real-time signals are formatted relative to `SIGRTMIN`, e.g. `SIGRTMIN+2`.
+/// Other signals are formatted using the platform specific name, or using the
+/// format of the `Debug` implementation if the name is unknown, e.g.
+/// `Other(40)`, so that it isn't parsed as a different signal.
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Signal::Realtime(0) => f.write_str("SIGRTMIN"),
+            Signal::Realtime(offset) => write!(f, "SIGRTMIN+{}", offset),
+            Signal::Other(raw_signal) => {
+                match OTHER_SIGNALS.iter().find(|(_, n)| *n == raw_signal) {
+                    Some((name, _)) => f.write_str(name),
+                    None => write!(f, "Other({})", raw_signal),
+                }
+            }
+            signal => {
+                // NOTE: all other signals are in `NAMES`.
+                let (name, _) = NAMES.iter().find(|(_, s)| *s == signal).unwrap();
+                write!(f, "SIG{}", name)
+            }
+        }
+    }
+}
+
+/// Returns `true` if `signal` is formatted using its name by the `Display`
+/// implementation, rather than a platform specific number.
+#[cfg(feature = "serde")]
+pub(crate) fn has_name(signal: Signal) -> bool {
+    match signal {
+        Signal::Realtime(offset) => offset <= Signal::MAX_REALTIME,
+        Signal::Other(raw_signal) => OTHER_SIGNALS.iter().any(|(_, n)| *n == raw_signal),
+        _ => true,
+    }
+}
+
+/// Parses a signal from its name or number.
+///
+/// The following formats are supported:
+///  * The name, with or without the `SIG` prefix, ignoring case, e.g.
+///    `SIGTERM`, `TERM` or `term`. This also includes names of signals
+///    without their own variant, e.g. `SIGURG`.
+///  * Real-time signals relative to `SIGRTMIN` or `SIGRTMAX`, e.g.
+///    `SIGRTMIN+2` or `RTMAX-1`.
+///  * The platform specific number, e.g. `15`.
+///  * The format used by the `Debug` implementation, e.g. `Terminate` or
+///    `Realtime(2)`.
+///
+/// # Examples
+///
+/// ```
+/// use mio_signals::Signal;
+///
+/// assert_eq!("SIGTERM".parse(), Ok(Signal::Terminate));
+/// assert_eq!("TERM".parse(), Ok(Signal::Terminate));
+/// assert_eq!("term".parse(), Ok(Signal::Terminate));
+/// assert_eq!("15".parse(), Ok(Signal::Terminate));
+/// assert_eq!("Terminate".parse(), Ok(Signal::Terminate));
+/// ```
+impl FromStr for Signal {
+    type Err = ParseSignalError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        if let Ok(number) = input.parse::<i32>() {
+            return Signal::from_number(number).ok_or(ParseSignalError { _priv: () });
+        }
+
+        if let Some(signal) = parse_debug(input) {
+            return Ok(signal);
+        }
+
+        let name = input.to_ascii_uppercase();
+        let name = name.strip_prefix("SIG").unwrap_or(&name);
+        if let Some((_, signal)) = NAMES.iter().find(|(n, _)| *n == name) {
+            return Ok(*signal);
+        }
+        if name == "CLD" {
+            // Alias for `CHLD` used on some platforms.
+            return Ok(Signal::Child);
+        }
+        if let Some(signal) = parse_realtime(name) {
+            return Ok(signal);
+        }
+        if let Some((_, raw_signal)) = OTHER_SIGNALS.iter().find(|(n, _)| &n[3..] == name) {
+            return Ok(sys::from_raw_signal(*raw_signal));
+        }
+        Err(ParseSignalError { _priv: () })
+    }
+}
+
+/// Formats the signal using its name, e.g. `SIGKILL` for
+/// [`SendableSignal::Kill`], see the [`Display`] implementation of [`Signal`].
+///
+/// [`Display`]: fmt::Display
+impl fmt::Display for SendableSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendableSignal::Signal(signal) => signal.fmt(f),
+            signal => {
+                // NOTE: all other signals are in `SENDABLE_NAMES`.
+                let (name, _, _) = SENDABLE_NAMES.iter().find(|(_, _, s)| s == signal).unwrap();
+                write!(f, "SIG{}", name)
+            }
+        }
+    }
+}
+
+/// Parses a signal from its name or number, see the [`FromStr`]
+/// implementation of [`Signal`].
+///
+/// # Examples
+///
+/// ```
+/// use mio_signals::{SendableSignal, Signal};
+///
+/// assert_eq!("SIGKILL".parse(), Ok(SendableSignal::Kill));
+/// assert_eq!("stop".parse(), Ok(SendableSignal::Stop));
+/// assert_eq!("Continue".parse(), Ok(SendableSignal::Continue));
+/// assert_eq!("SIGTERM".parse(), Ok(SendableSignal::Signal(Signal::Terminate)));
+/// ```
+impl FromStr for SendableSignal {
+    type Err = ParseSignalError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        if let Ok(signal) = input.parse::<Signal>() {
+            return Ok(signal.into());
+        }
+
+        let input = input.trim();
+        if let Ok(number) = input.parse::<i32>() {
+            return SendableSignal::from_number(number).ok_or(ParseSignalError { _priv: () });
+        }
+
+        // Format used by the `Debug` implementation, e.g. `Signal(Terminate)`.
+        if let Some((variant, signal)) = input.strip_suffix(')').and_then(|i| i.split_once('('))
+            && variant.eq_ignore_ascii_case("Signal")
+        {
+            return signal.parse::<Signal>().map(SendableSignal::from);
+        }
+
+        let name = input.to_ascii_uppercase();
+        let name = name.strip_prefix("SIG").unwrap_or(&name);
+        SENDABLE_NAMES
+            .iter()
+            .find(|(n, variant, _)| *n == name || variant.eq_ignore_ascii_case(input))
+            .map(|(_, _, signal)| *signal)
+            .ok_or(ParseSignalError { _priv: () })
+    }
+}
+
+/// Parse the format used by the `Debug` implementation of `Signal`.
+fn parse_debug(input: &str) -> Option<Signal> {
+    if let Some((_, signal)) = VARIANT_NAMES
+        .iter()
+        .find(|(n, _)| n.eq_ignore_ascii_case(input))
+    {
+        return Some(*signal);
+    }
+
+    let (variant, value) = input.strip_suffix(')')?.split_once('(')?;
+    if variant.eq_ignore_ascii_case("Realtime") {
+        value.parse().ok().and_then(realtime)
+    } else if variant.eq_ignore_ascii_case("Other") {
+        // NOTE: not using `Signal::from_number` as that could return a
+        // different variant, e.g. `Interrupt` for `Other(2)`.
+        value
+            .parse()
+            .ok()
+            .filter(|raw_signal| sys::is_valid_signal(*raw_signal))
+            .map(Signal::Other)
+    } else {
+        None
+    }
+}
+
+/// Parse a real-time signal, e.g. `RTMIN+1` or `RTMAX-1`, without the `SIG`
+/// prefix.
+fn parse_realtime(name: &str) -> Option<Signal> {
+    if let Some(offset) = name.strip_prefix("RTMIN") {
+        match offset.strip_prefix('+') {
+            Some(offset) => offset.parse().ok().and_then(realtime),
+            None if offset.is_empty() => Some(Signal::Realtime(0)),
+            None => None,
+        }
+    } else if let Some(offset) = name.strip_prefix("RTMAX") {
+        let offset: libc::c_int = match offset.strip_prefix('-') {
+            Some(offset) => offset.parse().ok()?,
+            None if offset.is_empty() => 0,
+            None => return None,
+        };
+        let range = sys::realtime_range()?;
+        let raw_signal = range.end() - offset;
+        range
+            .contains(&raw_signal)
+            .then(|| sys::from_raw_signal(raw_signal))
+    } else {
+        None
+    }
+}
+
+/// Returns `Signal::Realtime(offset)` if `offset` is not larger than
+/// `Signal::MAX_REALTIME`.
+fn realtime(offset: u8) -> Option<Signal> {
+    (offset <= Signal::MAX_REALTIME).then_some(Signal::Realtime(offset))
+}
+
+/// Parses a set of signals, separated by commas (`,`) or pipes (`|`).
+///
+/// Each signal is parsed using the [`FromStr`] implementation of [`Signal`].
+/// This also supports the format used by the `Debug` implementation of
+/// `SignalSet`, including `(empty)` for the empty set.
+///
+/// # Examples
+///
+/// ```
+/// use mio_signals::{Signal, SignalSet};
+///
+/// let set: SignalSet = "SIGINT, TERM, hup".parse().unwrap();
+/// assert_eq!(set, Signal::Interrupt | Signal::Terminate | Signal::Hangup);
+///
+/// let set: SignalSet = "Interrupt|Quit".parse().unwrap();
+/// assert_eq!(set, Signal::Interrupt | Signal::Quit);
+/// ```
+impl FromStr for SignalSet {
+    type Err = ParseSignalError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+        if input.is_empty() || input == "(empty)" {
+            return Ok(SignalSet::empty());
+        }
+
+        let mut set = SignalSet::empty();
+        for signal in input.split([',', '|']) {
+            let signal: Signal = signal.parse()?;
+            if set.try_insert(signal).is_none() {
+                return Err(ParseSignalError { _priv: () });
+            }
+        }
+        Ok(set)
+    }
+}
+
+/// Error returned when parsing a [`Signal`], [`SendableSignal`] or
+/// [`SignalSet`] fails.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseSignalError {
+    _priv: (),
+}
+
+impl fmt::Display for ParseSignalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid signal name or number")
+    }
+}
+
+impl Error for ParseSignalError {}
@@ -0,0 +1,164 @@
+//! Process file descriptors, see [`PidFd`].
+
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::process::Child;
+use std::{io, ptr};
+
+use log::error;
+use mio::unix::SourceFd;
+use mio::{Interest, Registry, Token, event};
+
+use crate::sys::sendable_signal;
+use crate::{SendError, SendableSignal};
+
+/// File descriptor referring to a process.
+///
+/// Unlike a process id a `PidFd` always refers to the same process, even
+/// after the process terminated and its process id is reused. This makes it
+/// possible to send signals to a process without the risk of signalling an
+/// unrelated process that reused the process id.
+///
+/// `PidFd` implements [`event::Source`], it becomes readable once the process
+/// terminates.
+///
+/// # Notes
+///
+/// This is only supported on Android and Linux, using [`pidfd_open(2)`] (Linux
+/// 5.3+) and [`pidfd_send_signal(2)`] (Linux 5.1+).
+///
+/// [`pidfd_open(2)`]: https://man7.org/linux/man-pages/man2/pidfd_open.2.html
+/// [`pidfd_send_signal(2)`]: https://man7.org/linux/man-pages/man2/pidfd_send_signal.2.html
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> std::io::Result<()> {
+/// use std::os::unix::process::ExitStatusExt;
+/// use std::process::Command;
+///
+/// use mio::{Events, Interest, Poll, Token};
+/// use mio_signals::{PidFd, Signal};
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let mut child = Command::new("sleep").arg("10").spawn()?;
+/// let mut pidfd = PidFd::from_child(&child)?;
+/// poll.registry().register(&mut pidfd, Token(0), Interest::READABLE)?;
+///
+/// pidfd.send_signal(Signal::Terminate)?;
+///
+/// // Wait until the process terminated.
+/// poll.poll(&mut events, None)?;
+/// let status = child.wait()?;
+/// assert_eq!(status.signal(), Some(libc::SIGTERM));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct PidFd {
+    fd: RawFd,
+}
+
+impl PidFd {
+    /// Open a `PidFd` for the process with `pid`.
+    ///
+    /// Note that the process id can be reused before this is called, it's only
+    /// race free if `pid` refers to a child process that isn't reaped yet.
+    /// See [`PidFd::from_child`].
+    pub fn open(pid: u32) -> io::Result<PidFd> {
+        let pid = match libc::pid_t::try_from(pid) {
+            Ok(pid) if pid > 0 => pid,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "invalid process id",
+                ));
+            }
+        };
+        let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, pid, 0) };
+        if fd == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(PidFd { fd: fd as RawFd })
+        }
+    }
+
+    /// Open a `PidFd` for the `child` process.
+    ///
+    /// This is race free as long as `child` isn't waited on (e.g. using
+    /// [`Child::wait`] or [`reap_children`]), as its process id can't be
+    /// reused before then.
+    ///
+    /// [`reap_children`]: crate::reap_children
+    pub fn from_child(child: &Child) -> io::Result<PidFd> {
+        PidFd::open(child.id())
+    }
+
+    /// Send `signal` to the process.
+    ///
+    /// This uses [`pidfd_send_signal(2)`], if the process already terminated
+    /// this returns [`SendError::NoSuchProcess`], even if the process id is
+    /// reused.
+    ///
+    /// [`pidfd_send_signal(2)`]: https://man7.org/linux/man-pages/man2/pidfd_send_signal.2.html
+    pub fn send_signal<S>(&self, signal: S) -> Result<(), SendError>
+    where
+        S: Into<SendableSignal>,
+    {
+        let raw_signal = sendable_signal(signal.into())?;
+        let res = unsafe {
+            libc::syscall(
+                libc::SYS_pidfd_send_signal,
+                self.fd,
+                raw_signal,
+                ptr::null::<libc::siginfo_t>(),
+                0,
+            )
+        };
+        if res == -1 {
+            Err(SendError::from(io::Error::last_os_error()))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl AsRawFd for PidFd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl event::Source for PidFd {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        SourceFd(&self.fd).register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        SourceFd(&self.fd).reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        SourceFd(&self.fd).deregister(registry)
+    }
+}
+
+impl Drop for PidFd {
+    fn drop(&mut self) {
+        if unsafe { libc::close(self.fd) } == -1 {
+            let err = io::Error::last_os_error();
+            error!("error closing PidFd: {}", err);
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! Reaping orphaned child processes, see [`Reaper`].
+
+use std::os::unix::process::ExitStatusExt;
+use std::process::{self, Child, ExitStatus};
+use std::{io, mem};
+
+use mio::{Interest, Registry, Token, event};
+
+use crate::{ChildExit, Signal, Signals};
+
+/// Reaps terminated child processes, except for the ones owned by someone
+/// else.
+///
+/// This is meant for processes that end up with child processes they didn't
+/// spawn themselves, i.e. processes running as PID 1 (see [`is_pid1`]) or as
+/// child subreaper (see [`set_child_subreaper`]). Orphaned processes are
+/// reparented to those processes and, once terminated, remain zombie processes
+/// until they are reaped.
+///
+/// `Reaper` listens for [`Signal::Child`] using [`Signals`] and implements
+/// [`event::Source`]. Child processes that are waited on by someone else, e.g.
+/// using [`Child::wait`] or a [`ChildWatcher`], must be marked as owned using
+/// [`Reaper::own`], otherwise the `Reaper` could reap them first.
+///
+/// [`ChildWatcher`]: crate::ChildWatcher
+///
+/// # Notes
+///
+/// On Android and Linux the child processes are read from `/proc`, skipping
+/// the owned child processes. If `/proc` isn't mounted, and on other
+/// platforms, this uses [`waitid(2)`] to
+/// determine which child process terminated, which only returns a single
+/// terminated child process at a time. If that is an owned child process all
+/// other terminated child processes are not reaped until the owned child
+/// process is reaped by its owner.
+///
+/// Because `Signal::Child` is used, the same notes about multithreaded
+/// processes apply as for [`Signals`], and no other `Signals` instance should
+/// listen for `Signal::Child`.
+///
+/// [`waitid(2)`]: https://man7.org/linux/man-pages/man2/waitid.2.html
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> std::io::Result<()> {
+/// use mio::{Events, Interest, Poll, Token};
+/// use mio_signals::{Reaper, is_pid1};
+///
+/// let mut poll = Poll::new()?;
+/// let mut events = Events::with_capacity(8);
+///
+/// let mut reaper = Reaper::new()?;
+/// poll.registry().register(&mut reaper, Token(0), Interest::READABLE)?;
+///
+/// if is_pid1() {
+///     // The kernel doesn't apply the default action (termination) for
+///     // signals send to PID 1, so we need to handle `SIGTERM` and `SIGINT`
+///     // ourselves.
+/// }
+///
+/// # let awakener = mio::Waker::new(poll.registry(), Token(1))?;
+/// # awakener.wake()?;
+/// poll.poll(&mut events, None)?;
+/// while let Some(exit) = reaper.receive()? {
+///     println!("reaped child process {}: {}", exit.pid(), exit.status());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Reaper {
+    signals: Signals,
+    /// Child processes owned by someone else.
+    owned: Vec<u32>,
+}
+
+impl Reaper {
+    /// Create a new `Reaper`.
+    pub fn new() -> io::Result<Reaper> {
+        Ok(Reaper {
+            signals: Signals::new(Signal::Child.into())?,
+            owned: Vec::new(),
+        })
+    }
+
+    /// Mark the `child` process as owned, see [`Reaper::own_pid`].
+    pub fn own(&mut self, child: &Child) {
+        self.own_pid(child.id());
+    }
+
+    /// Mark the child process with `pid` as owned, it will not be reaped by
+    /// the `Reaper`.
+    ///
+    /// The child process is automatically disowned once it's reaped by its
+    /// owner.
+    pub fn own_pid(&mut self, pid: u32) {
+        if !self.owned.contains(&pid) {
+            self.owned.push(pid);
+        }
+    }
+
+    /// Disown the child process with `pid`, returning `false` if it wasn't
+    /// owned.
+    pub fn disown(&mut self, pid: u32) -> bool {
+        match self.owned.iter().position(|owned| *owned == pid) {
+            Some(idx) => {
+                let _ = self.owned.swap_remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reap a terminated child process, that isn't owned.
+    ///
+    /// This returns `Ok(None)` if no (not owned) child process has terminated.
+    /// Because Mio uses edge triggers this must be called until it returns
+    /// `Ok(None)`.
+    pub fn receive(&mut self) -> io::Result<Option<ChildExit>> {
+        let _ = self.signals.drain()?;
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        if let Some(children) = children() {
+            for pid in children {
+                if !self.owned.contains(&pid)
+                    && let Some(exit) = reap_child(pid)?
+                {
+                    return Ok(Some(exit));
+                }
+            }
+            self.owned.retain(|pid| is_child(*pid));
+            return Ok(None);
+        }
+
+        match peek_child()? {
+            Some(pid) if self.owned.contains(&pid) => Ok(None),
+            Some(pid) => reap_child(pid),
+            None => {
+                // Remove the owned child processes that are reaped by their
+                // owner, so that we don't mistake a new process with the same
+                // process id as owned.
+                self.owned.retain(|pid| is_child(*pid));
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl event::Source for Reaper {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.signals.register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        self.signals.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.signals.deregister(registry)
+    }
+}
+
+/// Returns the process ids of all child processes of the calling process, or
+/// `None` if `/proc` isn't mounted.
+///
+/// This uses `/proc/self/task/*/children` if the kernel supports it, and
+/// otherwise looks at the parent process id of all processes.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn children() -> Option<Vec<u32>> {
+    let mut children = Vec::new();
+    for task in std::fs::read_dir("/proc/self/task").ok()? {
+        let task = task.ok()?.path();
+        match std::fs::read_to_string(task.join("children")) {
+            Ok(pids) => children.extend(
+                pids.split_whitespace()
+                    .filter_map(|pid| pid.parse::<u32>().ok()),
+            ),
+            // The thread stopped in the meantime.
+            Err(_) if !task.exists() => {}
+            Err(_) => return scan_children(),
+        }
+    }
+    Some(children)
+}
+
+/// Returns the process ids of all processes with the calling process as
+/// parent, see `children`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn scan_children() -> Option<Vec<u32>> {
+    let ppid = process::id();
+    let children = std::fs::read_dir("/proc")
+        .ok()?
+        .filter_map(|entry| {
+            let pid: u32 = entry.ok()?.file_name().to_str()?.parse().ok()?;
+            // NOTE: the process could have stopped in the meantime.
+            let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+            // The process name is between parentheses and can contain
+            // whitespace, the state and parent process id follow it.
+            let mut fields = stat.get(stat.rfind(')')? + 1..)?.split_whitespace();
+            let parent: u32 = fields.nth(1)?.parse().ok()?;
+            (parent == ppid).then_some(pid)
+        })
+        .collect();
+    Some(children)
+}
+
+/// Returns the process id of a terminated child process, without reaping it.
+fn peek_child() -> io::Result<Option<u32>> {
+    waitid(libc::P_ALL, 0).map(|info| {
+        // NOTE: `si_pid` is zero if no child process has terminated.
+        info.and_then(|info| match unsafe { info.si_pid() } {
+            0 => None,
+            pid => Some(pid as u32),
+        })
+    })
+}
+
+/// Returns `true` if `pid` is a child process that isn't reaped yet.
+fn is_child(pid: u32) -> bool {
+    waitid(libc::P_PID, pid as libc::id_t).is_ok_and(|info| info.is_some())
+}
+
+/// Calls `waitid(2)` without reaping the child process, returns `None` if no
+/// child processes exist.
+fn waitid(idtype: libc::idtype_t, id: libc::id_t) -> io::Result<Option<libc::siginfo_t>> {
+    let mut info: libc::siginfo_t = unsafe { mem::zeroed() };
+    let options = libc::WEXITED | libc::WNOHANG | libc::WNOWAIT;
+    loop {
+        if unsafe { libc::waitid(idtype, id, &mut info, options) } == -1 {
+            match io::Error::last_os_error() {
+                ref err if err.kind() == io::ErrorKind::Interrupted => continue,
+                ref err if err.raw_os_error() == Some(libc::ECHILD) => return Ok(None),
+                err => return Err(err),
+            }
+        }
+        return Ok(Some(info));
+    }
+}
+
+/// Reap the terminated child process with `pid`.
+fn reap_child(pid: u32) -> io::Result<Option<ChildExit>> {
+    let mut status = 0;
+    loop {
+        match unsafe { libc::waitpid(pid as libc::pid_t, &mut status, libc::WNOHANG) } {
+            -1 => match io::Error::last_os_error() {
+                ref err if err.kind() == io::ErrorKind::Interrupted => continue,
+                err => return Err(err),
+            },
+            0 => return Ok(None),
+            _ => {
+                return Ok(Some(ChildExit {
+                    pid,
+                    status: ExitStatus::from_raw(status),
+                }));
+            }
+        }
+    }
+}
+
+/// Returns `true` if the calling process is PID 1, i.e. the init process.
+///
+/// The kernel doesn't apply the default action for signals send to PID 1 (from
+/// within its PID namespace), e.g. sending `SIGTERM` to PID 1 doesn't
+/// terminate it unless it handles the signal itself. Use [`Signals`] to handle
+/// those signals.
+pub fn is_pid1() -> bool {
+    process::id() == 1
+}
+
+/// Set or unset the calling process as child subreaper.
+///
+/// A child subreaper becomes the parent of orphaned descendant processes,
+/// rather than the init process, see [`Reaper`] to reap them.
+///
+/// This uses [`PR_SET_CHILD_SUBREAPER`] and is only supported on Android and
+/// Linux.
+///
+/// [`PR_SET_CHILD_SUBREAPER`]: https://man7.org/linux/man-pages/man2/PR_SET_CHILD_SUBREAPER.2const.html
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn set_child_subreaper(subreaper: bool) -> io::Result<()> {
+    let arg = libc::c_ulong::from(subreaper);
+    if unsafe { libc::prctl(libc::PR_SET_CHILD_SUBREAPER, arg, 0, 0, 0) } == -1 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
+/// Returns `true` if the calling process is a child subreaper, see
+/// [`set_child_subreaper`].
+///
+/// This is only supported on Android and Linux.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn is_child_subreaper() -> io::Result<bool> {
+    let mut subreaper: libc::c_int = 0;
+    if unsafe { libc::prctl(libc::PR_GET_CHILD_SUBREAPER, &mut subreaper, 0, 0, 0) } == -1 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(subreaper != 0)
+    }
+}
@@ -0,0 +1,129 @@
+//! Implementations of `Serialize` and `Deserialize`.
+//!
+//! Signals are serialised using their name, e.g. `"SIGUSR1"`, as the signal
+//! numbers differ per platform. Serialising a signal without a name, e.g.
+//! `Signal::Other(40)`, returns an error. Signal sets are serialised as a
+//! sequence of signals.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{self, Serialize, SerializeSeq, Serializer};
+
+use crate::parse::has_name;
+use crate::{SendableSignal, Signal, SignalSet};
+
+impl Serialize for Signal {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if !has_name(*self) {
+            return Err(ser::Error::custom(format_args!(
+                "signal {} has no portable name",
+                self
+            )));
+        }
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Signal {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(SignalVisitor(PhantomData))
+    }
+}
+
+impl Serialize for SendableSignal {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            SendableSignal::Signal(signal) => signal.serialize(serializer),
+            signal => serializer.collect_str(signal),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SendableSignal {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(SignalVisitor(PhantomData))
+    }
+}
+
+/// Visitor for [`Signal`] and [`SendableSignal`], parsing the signal name.
+struct SignalVisitor<T>(PhantomData<T>);
+
+impl<T: FromStr> Visitor<'_> for SignalVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a signal name")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        value
+            .parse()
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &self))
+    }
+}
+
+impl Serialize for SignalSet {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for signal in *self {
+            seq.serialize_element(&signal)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for SignalSet {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SignalSetVisitor)
+    }
+}
+
+struct SignalSetVisitor;
+
+impl<'de> Visitor<'de> for SignalSetVisitor {
+    type Value = SignalSet;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a sequence of signal names")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut set = SignalSet::empty();
+        while let Some(signal) = seq.next_element::<Signal>()? {
+            if set.try_insert(signal).is_none() {
+                return Err(de::Error::custom(format_args!(
+                    "signal {} can't be part of a signal set",
+                    signal
+                )));
+            }
+        }
+        Ok(set)
+    }
+}
@@ -0,0 +1,155 @@
+use std::io;
+
+use mio::{Interest, Registry, Token, event};
+
+use crate::{Backend, Signal, SignalInfo, SignalSet};
+
+#[cfg(any(
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+use super::kqueue;
+use super::selfpipe;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use super::signalfd;
+
+/// Signaler using one of the backends, see `Backend`.
+///
+/// # Implementation notes
+///
+/// On Android and Linux the `signalfd(2)` implementation is preferred as it
+/// provides the most information about received signals, but it relies on the
+/// signals being blocked in all threads. This can only be done if no other
+/// threads exist yet, as the blocked signals are inherited by spawned threads.
+/// If other threads already exist `Backend::Auto` uses the self-pipe
+/// implementation instead.
+#[derive(Debug)]
+pub enum Signals {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    SignalFd(signalfd::Signals),
+    #[cfg(any(
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    Kqueue(kqueue::Signals),
+    SelfPipe(selfpipe::Signals),
+}
+
+/// Evaluate `$call` with `$signals` bound to the backend's `Signals`.
+macro_rules! delegate {
+    ($self: expr, $signals: ident => $call: expr) => {
+        match $self {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Signals::SignalFd($signals) => $call,
+            #[cfg(any(
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "ios",
+                target_os = "macos",
+                target_os = "netbsd",
+                target_os = "openbsd"
+            ))]
+            Signals::Kqueue($signals) => $call,
+            Signals::SelfPipe($signals) => $call,
+        }
+    };
+}
+
+impl Signals {
+    pub fn new(signals: SignalSet, backend: Backend) -> io::Result<Signals> {
+        match backend {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Backend::SignalFd => signalfd::Signals::new(signals).map(Signals::SignalFd),
+            #[cfg(not(any(target_os = "linux", target_os = "android")))]
+            Backend::SignalFd => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "signalfd is only supported on Android and Linux",
+            )),
+            Backend::SelfPipe => selfpipe::Signals::new(signals).map(Signals::SelfPipe),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Backend::Auto if is_single_threaded() => {
+                signalfd::Signals::new(signals).map(Signals::SignalFd)
+            }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            Backend::Auto => selfpipe::Signals::new(signals).map(Signals::SelfPipe),
+            #[cfg(any(
+                target_os = "dragonfly",
+                target_os = "freebsd",
+                target_os = "ios",
+                target_os = "macos",
+                target_os = "netbsd",
+                target_os = "openbsd"
+            ))]
+            Backend::Auto => kqueue::Signals::new(signals).map(Signals::Kqueue),
+        }
+    }
+
+    pub fn receive(&mut self) -> io::Result<Option<Signal>> {
+        delegate!(self, signals => signals.receive())
+    }
+
+    pub fn receive_info(&mut self) -> io::Result<Option<SignalInfo>> {
+        delegate!(self, signals => signals.receive_info())
+    }
+
+    pub fn receive_many(&mut self, infos: &mut [SignalInfo]) -> io::Result<usize> {
+        delegate!(self, signals => signals.receive_many(infos))
+    }
+
+    /// Whether or not the sender of a signal is known, see
+    /// `Signals::set_sender_filter`.
+    pub const fn sender_info(&self) -> bool {
+        delegate!(self, signals => signals.sender_info())
+    }
+
+    pub fn drain<F>(&mut self, count: F) -> io::Result<()>
+    where
+        F: FnMut(SignalInfo, u32),
+    {
+        delegate!(self, signals => signals.drain(count))
+    }
+}
+
+/// Returns `true` if the calling thread is the only thread in the process.
+///
+/// If this can't be determined, e.g. if `/proc` isn't mounted, this assumes
+/// it is.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn is_single_threaded() -> bool {
+    match std::fs::read_dir("/proc/self/task") {
+        Ok(tasks) => tasks.take(2).count() <= 1,
+        Err(_) => true,
+    }
+}
+
+impl event::Source for Signals {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        delegate!(self, signals => signals.register(registry, token, interests))
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        delegate!(self, signals => signals.reregister(registry, token, interests))
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        delegate!(self, signals => signals.deregister(registry))
+    }
+}
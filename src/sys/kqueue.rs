@@ -6,7 +6,7 @@ use log::error;
 use mio::unix::SourceFd;
 use mio::{Interest, Registry, Token, event};
 
-use crate::{Signal, SignalSet};
+use crate::{Signal, SignalInfo, SignalSet};
 
 use super::{from_raw_signal, raw_signal};
 
@@ -38,6 +38,16 @@ impl Signals {
     }
 
     pub fn receive(&mut self) -> io::Result<Option<Signal>> {
+        self.receive_info()
+            .map(|info| info.map(|info| info.signal()))
+    }
+
+    /// `EVFILT_SIGNAL` doesn't provide the sender of the signal.
+    pub const fn sender_info(&self) -> bool {
+        false
+    }
+
+    pub fn receive_info(&mut self) -> io::Result<Option<SignalInfo>> {
         let mut kevent: MaybeUninit<libc::kevent> = MaybeUninit::uninit();
         // No blocking.
         let timeout = libc::timespec {
@@ -57,13 +67,81 @@ impl Signals {
                 // Should never happen, but just in case.
                 let filter = kevent.filter; // Can't create ref to packed struct.
                 debug_assert_eq!(filter, libc::EVFILT_SIGNAL);
-                // This should never return `None` as we control the signals we
-                // register for, which is always defined in terms of `Signal`.
-                Ok(from_raw_signal(kevent.ident as libc::c_int))
+                let signal = from_raw_signal(kevent.ident as libc::c_int);
+                // NOTE: `EVFILT_SIGNAL` doesn't provide any information about
+                // the sender, so we leave all that unset.
+                Ok(Some(SignalInfo::new(signal)))
             }
             _ => unreachable!("unexpected number of events"),
         }
     }
+
+    pub fn receive_many(&mut self, infos: &mut [SignalInfo]) -> io::Result<usize> {
+        let mut kevents: [MaybeUninit<libc::kevent>; MAX_SIGNALS] =
+            [MaybeUninit::uninit(); MAX_SIGNALS];
+        let n = infos.len().min(MAX_SIGNALS);
+        let kevents = self.receive_events(&mut kevents[..n])?;
+        for (info, kevent) in infos.iter_mut().zip(kevents) {
+            *info = SignalInfo::new(from_raw_signal(kevent.ident as libc::c_int));
+        }
+        Ok(kevents.len())
+    }
+
+    /// Receive all signals, calling `count` for each one.
+    pub fn drain<F>(&mut self, mut count: F) -> io::Result<()>
+    where
+        F: FnMut(SignalInfo, u32),
+    {
+        let mut kevents: [MaybeUninit<libc::kevent>; MAX_SIGNALS] =
+            [MaybeUninit::uninit(); MAX_SIGNALS];
+        for kevent in self.receive_events(&mut kevents)? {
+            let signal = from_raw_signal(kevent.ident as libc::c_int);
+            // `data` holds the number of times the signal occurred since the
+            // last time it was returned.
+            let n = kevent.data.try_into().unwrap_or(u32::MAX);
+            count(SignalInfo::new(signal), n);
+        }
+        Ok(())
+    }
+
+    /// Receive as many events as fit in `kevents`, without blocking.
+    ///
+    /// Every signal results in at most a single event, so if `kevents` can hold
+    /// `MAX_SIGNALS` events this receives all signals.
+    fn receive_events<'a>(
+        &mut self,
+        kevents: &'a mut [MaybeUninit<libc::kevent>],
+    ) -> io::Result<&'a [libc::kevent]> {
+        if kevents.is_empty() {
+            return Ok(&[]);
+        }
+        // No blocking.
+        let timeout = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+
+        let n_events = unsafe {
+            libc::kevent(
+                self.kq,
+                ptr::null(),
+                0,
+                kevents[0].as_mut_ptr(),
+                kevents.len() as _,
+                &timeout,
+            )
+        };
+        if n_events == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            // This is safe because `kevent` ensures that the first `n_events`
+            // events are initialised.
+            let kevents = &kevents[..n_events as usize];
+            Ok(unsafe {
+                &*(kevents as *const [MaybeUninit<libc::kevent>] as *const [libc::kevent])
+            })
+        }
+    }
 }
 
 fn new_kqueue() -> io::Result<RawFd> {
@@ -75,11 +153,14 @@ fn new_kqueue() -> io::Result<RawFd> {
     }
 }
 
+/// Maximum number of signals in a `SignalSet`.
+const MAX_SIGNALS: usize = u128::BITS as usize;
+
 fn register_signals(kq: RawFd, signals: SignalSet) -> io::Result<()> {
     // For each signal create an kevent to indicate we want events for
     // those signals.
-    let mut changes: [MaybeUninit<libc::kevent>; SignalSet::all().len()] =
-        [MaybeUninit::uninit(); SignalSet::all().len()];
+    let mut changes: [MaybeUninit<libc::kevent>; MAX_SIGNALS] =
+        [MaybeUninit::uninit(); MAX_SIGNALS];
     let mut n_changes = 0;
     for signal in signals {
         changes[n_changes] = MaybeUninit::new(libc::kevent {
@@ -137,6 +218,11 @@ fn unignore_signals(signals: SignalSet) -> io::Result<()> {
 
 /// Call `sigaction` for each signal in `signals`, using `action` as signal
 /// handler.
+///
+/// Signals that are ignored by default (`SIGCHLD` and `SIGWINCH`) are skipped,
+/// `EVFILT_SIGNAL` receives those without changing their action. Furthermore
+/// for `SIGCHLD` setting `SIG_IGN` has the side effect of automatically
+/// reaping all child processes.
 fn sigaction(signals: SignalSet, action: libc::sighandler_t) -> io::Result<()> {
     let action = libc::sigaction {
         sa_sigaction: action,
@@ -144,6 +230,9 @@ fn sigaction(signals: SignalSet, action: libc::sighandler_t) -> io::Result<()> {
         sa_flags: 0,
     };
     for signal in signals {
+        if ignored_by_default(signal) {
+            continue;
+        }
         if unsafe { libc::sigaction(raw_signal(signal), &action, ptr::null_mut()) } == -1 {
             return Err(io::Error::last_os_error());
         }
@@ -151,6 +240,11 @@ fn sigaction(signals: SignalSet, action: libc::sighandler_t) -> io::Result<()> {
     Ok(())
 }
 
+/// Returns `true` if the default action of `signal` is to ignore it.
+const fn ignored_by_default(signal: Signal) -> bool {
+    matches!(signal, Signal::Child | Signal::WindowChange)
+}
+
 /// Create an empty `sigset_t`.
 fn empty_sigset() -> io::Result<libc::sigset_t> {
     let mut set: MaybeUninit<libc::sigset_t> = MaybeUninit::uninit();
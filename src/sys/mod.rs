@@ -1,6 +1,6 @@
 //! Platform dependent implementation of Signals.
 
-use crate::Signal;
+use crate::{ChildExit, PipePolicy, SendableSignal, Signal, SignalValue, WindowSize};
 
 #[cfg(any(
     target_os = "dragonfly",
@@ -12,66 +12,411 @@ use crate::Signal;
 ))]
 mod kqueue;
 
-#[cfg(any(
-    target_os = "dragonfly",
-    target_os = "freebsd",
-    target_os = "ios",
-    target_os = "macos",
-    target_os = "netbsd",
-    target_os = "openbsd"
-))]
-pub use self::kqueue::Signals;
-
 #[cfg(any(target_os = "linux", target_os = "android"))]
 mod signalfd;
 
+#[cfg(unix)]
+mod selfpipe;
+
+#[cfg(unix)]
+mod backend;
+
+#[cfg(unix)]
+pub use self::backend::Signals;
+
+#[cfg(unix)]
+pub fn send_signal(pid: libc::pid_t, signal: SendableSignal) -> std::io::Result<()> {
+    kill(pid, sendable_signal(signal)?)
+}
+
+/// Send the null signal to `pid`, checking if it exists.
+#[cfg(unix)]
+pub fn probe_process(pid: libc::pid_t) -> std::io::Result<()> {
+    kill(pid, 0)
+}
+
+#[cfg(unix)]
+fn kill(pid: libc::pid_t, raw_signal: libc::c_int) -> std::io::Result<()> {
+    if unsafe { libc::kill(pid, raw_signal) } != 0 {
+        Err(std::io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn send_signal_to_thread(tid: libc::pid_t, signal: SendableSignal) -> std::io::Result<()> {
+    // NOTE: not all libc implementations provide `tgkill` or `gettid`, so we
+    // use the system calls directly.
+    let res = unsafe {
+        libc::syscall(
+            libc::SYS_tgkill,
+            libc::getpid(),
+            tid,
+            sendable_signal(signal)? as libc::c_long,
+        )
+    };
+    if res != 0 {
+        Err(std::io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
 #[cfg(any(target_os = "linux", target_os = "android"))]
-pub use self::signalfd::Signals;
+pub fn thread_id() -> u32 {
+    // This can't fail.
+    unsafe { libc::syscall(libc::SYS_gettid) as u32 }
+}
 
 #[cfg(unix)]
-pub fn send_signal(pid: u32, signal: Signal) -> std::io::Result<()> {
-    if unsafe { libc::kill(pid as libc::pid_t, raw_signal(signal)) } != 0 {
+pub fn send_signal_to_pthread(
+    thread: libc::pthread_t,
+    signal: SendableSignal,
+) -> std::io::Result<()> {
+    let raw_signal = sendable_signal(signal)?;
+    let errno = unsafe { libc::pthread_kill(thread, raw_signal) };
+    if errno != 0 {
+        Err(std::io::Error::from_raw_os_error(errno))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+pub fn raise(signal: SendableSignal) -> std::io::Result<()> {
+    let raw_signal = sendable_signal(signal)?;
+    if unsafe { libc::raise(raw_signal) } != 0 {
+        Err(std::io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd"
+))]
+pub fn send_signal_with_value(
+    pid: libc::pid_t,
+    signal: SendableSignal,
+    value: SignalValue,
+) -> std::io::Result<()> {
+    let value = libc::sigval {
+        sival_ptr: value.as_ptr(),
+    };
+    let raw_signal = sendable_signal(signal)?;
+    if unsafe { libc::sigqueue(pid, raw_signal, value) } != 0 {
         Err(std::io::Error::last_os_error())
     } else {
         Ok(())
     }
 }
 
+/// `sigqueue(3)` is not supported.
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "netbsd"
+)))]
+pub fn send_signal_with_value(
+    _: libc::pid_t,
+    _: SendableSignal,
+    _: SignalValue,
+) -> std::io::Result<()> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+/// Sets the action of `SIGPIPE` according to the [`PipePolicy`], restoring the
+/// original action when dropped.
+#[cfg(unix)]
+pub struct PipeGuard {
+    original: libc::sigaction,
+}
+
+#[cfg(unix)]
+impl PipeGuard {
+    pub fn new(policy: PipePolicy) -> std::io::Result<PipeGuard> {
+        let mut original: std::mem::MaybeUninit<libc::sigaction> = std::mem::MaybeUninit::uninit();
+        let action = match policy {
+            PipePolicy::Receive => None,
+            PipePolicy::Default => Some(libc::SIG_DFL),
+            PipePolicy::Ignore => Some(libc::SIG_IGN),
+        };
+        let action = action.map(|handler| {
+            let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+            action.sa_sigaction = handler;
+            action
+        });
+        let action_ptr = action
+            .as_ref()
+            .map_or(std::ptr::null(), |action| action as *const _);
+        if unsafe { libc::sigaction(libc::SIGPIPE, action_ptr, original.as_mut_ptr()) } == -1 {
+            Err(std::io::Error::last_os_error())
+        } else {
+            // This is safe because `sigaction` initialised it for us.
+            let original = unsafe { original.assume_init() };
+            Ok(PipeGuard { original })
+        }
+    }
+}
+
+#[cfg(unix)]
+impl std::fmt::Debug for PipeGuard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PipeGuard")
+            .field("original", &self.original.sa_sigaction)
+            .finish()
+    }
+}
+
+#[cfg(unix)]
+impl Drop for PipeGuard {
+    fn drop(&mut self) {
+        if unsafe { libc::sigaction(libc::SIGPIPE, &self.original, std::ptr::null_mut()) } == -1 {
+            let err = std::io::Error::last_os_error();
+            log::error!("error restoring SIGPIPE action: {}", err);
+        }
+    }
+}
+
+/// Reap a single terminated child process, if any.
+#[cfg(unix)]
+pub fn reap_child() -> Option<ChildExit> {
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::ExitStatus;
+
+    let mut status = 0;
+    loop {
+        match unsafe { libc::waitpid(-1, &mut status, libc::WNOHANG) } {
+            // No terminated child processes (0) or no child processes at all
+            // (-1 with `ECHILD`).
+            0 => return None,
+            -1 => match std::io::Error::last_os_error().raw_os_error() {
+                Some(libc::EINTR) => continue,
+                _ => return None,
+            },
+            pid => {
+                return Some(ChildExit {
+                    pid: pid as u32,
+                    status: ExitStatus::from_raw(status),
+                });
+            }
+        }
+    }
+}
+
+/// Get the size of the controlling terminal.
+#[cfg(unix)]
+pub fn window_size() -> std::io::Result<WindowSize> {
+    let fd = unsafe {
+        libc::open(
+            c"/dev/tty".as_ptr(),
+            libc::O_RDONLY | libc::O_NOCTTY | libc::O_CLOEXEC,
+        )
+    };
+    if fd == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+    let res = if unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, &mut size) } == -1 {
+        Err(std::io::Error::last_os_error())
+    } else {
+        Ok(WindowSize {
+            rows: size.ws_row,
+            columns: size.ws_col,
+            width: size.ws_xpixel,
+            height: size.ws_ypixel,
+        })
+    };
+    // Errors are ignored here as we only read from the file descriptor.
+    let _ = unsafe { libc::close(fd) };
+    res
+}
+
 // TODO: add Windows implementation.
 
+/// Signal number used for signals that are not supported, using it in system
+/// calls results in an `EINVAL` error.
+const INVALID_SIGNAL: libc::c_int = -1;
+
+/// Range of real-time signals, `SIGRTMIN..=SIGRTMAX`, if supported.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn realtime_range() -> Option<std::ops::RangeInclusive<libc::c_int>> {
+    Some(libc::SIGRTMIN()..=libc::SIGRTMAX())
+}
+
+/// Range of real-time signals, `SIGRTMIN..=SIGRTMAX`, if supported.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub fn realtime_range() -> Option<std::ops::RangeInclusive<libc::c_int>> {
+    None
+}
+
+/// Largest signal number on any of the supported platforms.
+pub const MAX_RAW_SIGNAL: libc::c_int = 128;
+
+/// Names of the signals that don't have their own variant in `Signal`.
+///
+/// `SIGKILL` and `SIGSTOP` are not included as they can't be caught, see
+/// `SendableSignal`.
+pub const OTHER_SIGNALS: &[(&str, libc::c_int)] = &[
+    ("SIGABRT", libc::SIGABRT),
+    ("SIGALRM", libc::SIGALRM),
+    ("SIGBUS", libc::SIGBUS),
+    ("SIGCONT", libc::SIGCONT),
+    ("SIGFPE", libc::SIGFPE),
+    ("SIGILL", libc::SIGILL),
+    ("SIGIO", libc::SIGIO),
+    ("SIGPROF", libc::SIGPROF),
+    ("SIGSEGV", libc::SIGSEGV),
+    ("SIGSYS", libc::SIGSYS),
+    ("SIGTRAP", libc::SIGTRAP),
+    ("SIGTSTP", libc::SIGTSTP),
+    ("SIGTTIN", libc::SIGTTIN),
+    ("SIGTTOU", libc::SIGTTOU),
+    ("SIGURG", libc::SIGURG),
+    ("SIGVTALRM", libc::SIGVTALRM),
+    ("SIGXCPU", libc::SIGXCPU),
+    ("SIGXFSZ", libc::SIGXFSZ),
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    ("SIGPWR", libc::SIGPWR),
+    #[cfg(all(
+        any(target_os = "linux", target_os = "android"),
+        not(any(target_arch = "mips", target_arch = "mips64", target_arch = "sparc64"))
+    ))]
+    ("SIGSTKFLT", libc::SIGSTKFLT),
+    #[cfg(any(
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    ("SIGEMT", libc::SIGEMT),
+    #[cfg(any(
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    ("SIGINFO", libc::SIGINFO),
+];
+
+/// Returns `true` if `raw_signal` is a valid signal number on this platform.
+pub fn is_valid_signal(raw_signal: libc::c_int) -> bool {
+    let mut set: std::mem::MaybeUninit<libc::sigset_t> = std::mem::MaybeUninit::uninit();
+    // NOTE: `sigaddset` returns `EINVAL` for invalid signal numbers, including
+    // the ones reserved by the C library.
+    unsafe {
+        libc::sigemptyset(set.as_mut_ptr()) == 0
+            && libc::sigaddset(set.as_mut_ptr(), raw_signal) == 0
+    }
+}
+
+/// Returns `true` if `raw_signal` can't be caught, blocked or ignored.
+pub const fn is_uncatchable(raw_signal: libc::c_int) -> bool {
+    matches!(raw_signal, libc::SIGKILL | libc::SIGSTOP)
+}
+
 /// Convert a `signal` into a Unix signal.
-fn raw_signal(signal: Signal) -> libc::c_int {
+///
+/// Returns `INVALID_SIGNAL` for signals that can't be caught, e.g.
+/// `Other(SIGKILL)`.
+pub fn raw_signal(signal: Signal) -> libc::c_int {
     match signal {
         Signal::Interrupt => libc::SIGINT,
         Signal::Quit => libc::SIGQUIT,
         Signal::Terminate => libc::SIGTERM,
         Signal::User1 => libc::SIGUSR1,
         Signal::User2 => libc::SIGUSR2,
+        Signal::Hangup => libc::SIGHUP,
+        Signal::Child => libc::SIGCHLD,
+        Signal::WindowChange => libc::SIGWINCH,
+        Signal::Pipe => libc::SIGPIPE,
+        Signal::Other(raw_signal) if is_uncatchable(raw_signal) => INVALID_SIGNAL,
+        Signal::Other(raw_signal) => raw_signal,
+        Signal::Realtime(offset) => match realtime_range() {
+            Some(range) if range.contains(&(range.start() + libc::c_int::from(offset))) => {
+                range.start() + libc::c_int::from(offset)
+            }
+            _ => INVALID_SIGNAL,
+        },
+    }
+}
+
+/// Convert a `signal` into a Unix signal.
+pub fn raw_sendable_signal(signal: SendableSignal) -> libc::c_int {
+    match signal {
+        SendableSignal::Signal(signal) => raw_signal(signal),
+        SendableSignal::Kill => libc::SIGKILL,
+        SendableSignal::Stop => libc::SIGSTOP,
+        SendableSignal::Continue => libc::SIGCONT,
+    }
+}
+
+/// Convert a `signal` into a Unix signal to send, returning an `EINVAL` error
+/// if it's not a valid signal number, e.g. `Other(0)` (the null signal).
+pub fn sendable_signal(signal: SendableSignal) -> std::io::Result<libc::c_int> {
+    let raw_signal = raw_sendable_signal(signal);
+    if is_valid_signal(raw_signal) {
+        Ok(raw_signal)
+    } else {
+        Err(std::io::Error::from_raw_os_error(libc::EINVAL))
     }
 }
 
 /// Convert a raw Unix signal into a signal.
-fn from_raw_signal(raw_signal: libc::c_int) -> Option<Signal> {
+pub fn from_raw_signal(raw_signal: libc::c_int) -> Signal {
     match raw_signal {
-        libc::SIGINT => Some(Signal::Interrupt),
-        libc::SIGQUIT => Some(Signal::Quit),
-        libc::SIGTERM => Some(Signal::Terminate),
-        libc::SIGUSR1 => Some(Signal::User1),
-        libc::SIGUSR2 => Some(Signal::User2),
-        _ => None,
+        libc::SIGINT => Signal::Interrupt,
+        libc::SIGQUIT => Signal::Quit,
+        libc::SIGTERM => Signal::Terminate,
+        libc::SIGUSR1 => Signal::User1,
+        libc::SIGUSR2 => Signal::User2,
+        libc::SIGHUP => Signal::Hangup,
+        libc::SIGCHLD => Signal::Child,
+        libc::SIGWINCH => Signal::WindowChange,
+        libc::SIGPIPE => Signal::Pipe,
+        raw_signal => realtime_range()
+            .filter(|range| range.contains(&raw_signal))
+            .and_then(|range| u8::try_from(raw_signal - range.start()).ok())
+            .filter(|offset| *offset <= Signal::MAX_REALTIME)
+            .map_or(Signal::Other(raw_signal), Signal::Realtime),
     }
 }
 
 #[test]
 fn test_from_raw_signal() {
-    assert_eq!(from_raw_signal(libc::SIGINT), Some(Signal::Interrupt));
-    assert_eq!(from_raw_signal(libc::SIGQUIT), Some(Signal::Quit));
-    assert_eq!(from_raw_signal(libc::SIGTERM), Some(Signal::Terminate));
-    assert_eq!(from_raw_signal(libc::SIGUSR1), Some(Signal::User1));
-    assert_eq!(from_raw_signal(libc::SIGUSR2), Some(Signal::User2));
+    assert_eq!(from_raw_signal(libc::SIGINT), Signal::Interrupt);
+    assert_eq!(from_raw_signal(libc::SIGQUIT), Signal::Quit);
+    assert_eq!(from_raw_signal(libc::SIGTERM), Signal::Terminate);
+    assert_eq!(from_raw_signal(libc::SIGUSR1), Signal::User1);
+    assert_eq!(from_raw_signal(libc::SIGUSR2), Signal::User2);
+    assert_eq!(from_raw_signal(libc::SIGHUP), Signal::Hangup);
+    assert_eq!(from_raw_signal(libc::SIGCHLD), Signal::Child);
+    assert_eq!(from_raw_signal(libc::SIGWINCH), Signal::WindowChange);
+    assert_eq!(from_raw_signal(libc::SIGPIPE), Signal::Pipe);
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        assert_eq!(from_raw_signal(libc::SIGRTMIN()), Signal::Realtime(0));
+        assert_eq!(from_raw_signal(libc::SIGRTMIN() + 2), Signal::Realtime(2));
+        assert_eq!(
+            from_raw_signal(libc::SIGRTMAX()),
+            Signal::Realtime((libc::SIGRTMAX() - libc::SIGRTMIN()) as u8)
+        );
+    }
 
-    // Unsupported signals.
-    assert_eq!(from_raw_signal(libc::SIGSTOP), None);
+    // Signals without their own variant.
+    assert_eq!(from_raw_signal(libc::SIGSTOP), Signal::Other(libc::SIGSTOP));
+    assert_eq!(from_raw_signal(libc::SIGURG), Signal::Other(libc::SIGURG));
 }
 
 #[test]
@@ -81,28 +426,37 @@ fn test_raw_signal() {
     assert_eq!(raw_signal(Signal::Terminate), libc::SIGTERM);
     assert_eq!(raw_signal(Signal::User1), libc::SIGUSR1);
     assert_eq!(raw_signal(Signal::User2), libc::SIGUSR2);
+    assert_eq!(raw_signal(Signal::Hangup), libc::SIGHUP);
+    assert_eq!(raw_signal(Signal::Child), libc::SIGCHLD);
+    assert_eq!(raw_signal(Signal::WindowChange), libc::SIGWINCH);
+    assert_eq!(raw_signal(Signal::Pipe), libc::SIGPIPE);
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        assert_eq!(raw_signal(Signal::Realtime(0)), libc::SIGRTMIN());
+        assert_eq!(raw_signal(Signal::Realtime(2)), libc::SIGRTMIN() + 2);
+    }
+    assert_eq!(raw_signal(Signal::Other(libc::SIGURG)), libc::SIGURG);
+    // Can't be caught.
+    assert_eq!(raw_signal(Signal::Other(libc::SIGKILL)), INVALID_SIGNAL);
+    assert_eq!(raw_signal(Signal::Other(libc::SIGSTOP)), INVALID_SIGNAL);
+
+    // Outside of the range of real-time signals.
+    assert_eq!(
+        raw_signal(Signal::Realtime(Signal::MAX_REALTIME)),
+        INVALID_SIGNAL
+    );
 }
 
 #[test]
 fn raw_signal_round_trip() {
-    assert_eq!(
-        raw_signal(from_raw_signal(libc::SIGINT).unwrap()),
-        libc::SIGINT
-    );
-    assert_eq!(
-        raw_signal(from_raw_signal(libc::SIGQUIT).unwrap()),
-        libc::SIGQUIT
-    );
-    assert_eq!(
-        raw_signal(from_raw_signal(libc::SIGTERM).unwrap()),
-        libc::SIGTERM
-    );
-    assert_eq!(
-        raw_signal(from_raw_signal(libc::SIGUSR1).unwrap()),
-        libc::SIGUSR1
-    );
-    assert_eq!(
-        raw_signal(from_raw_signal(libc::SIGUSR2).unwrap()),
-        libc::SIGUSR2
-    );
+    assert_eq!(raw_signal(from_raw_signal(libc::SIGINT)), libc::SIGINT);
+    assert_eq!(raw_signal(from_raw_signal(libc::SIGQUIT)), libc::SIGQUIT);
+    assert_eq!(raw_signal(from_raw_signal(libc::SIGTERM)), libc::SIGTERM);
+    assert_eq!(raw_signal(from_raw_signal(libc::SIGUSR1)), libc::SIGUSR1);
+    assert_eq!(raw_signal(from_raw_signal(libc::SIGUSR2)), libc::SIGUSR2);
+    assert_eq!(raw_signal(from_raw_signal(libc::SIGHUP)), libc::SIGHUP);
+    assert_eq!(raw_signal(from_raw_signal(libc::SIGCHLD)), libc::SIGCHLD);
+    assert_eq!(raw_signal(from_raw_signal(libc::SIGWINCH)), libc::SIGWINCH);
+    assert_eq!(raw_signal(from_raw_signal(libc::SIGPIPE)), libc::SIGPIPE);
 }
@@ -0,0 +1,447 @@
+use std::mem::{self, MaybeUninit, size_of};
+use std::os::unix::io::RawFd;
+use std::sync::atomic::{AtomicBool, AtomicI32, AtomicUsize, Ordering};
+use std::{fmt, io, ptr, thread};
+
+use log::error;
+use mio::unix::SourceFd;
+use mio::{Interest, Registry, Token, event};
+
+use crate::{Signal, SignalInfo, SignalSet};
+
+use super::{MAX_RAW_SIGNAL, from_raw_signal, is_valid_signal, raw_signal};
+
+/// Number of signal slots in the static arrays below, indexed by the raw
+/// signal number.
+const SLOTS: usize = MAX_RAW_SIGNAL as usize + 1;
+
+/// Write end of the pipe for each signal, or -1 if the signal isn't handled.
+static PIPES: [AtomicI32; SLOTS] = [const { AtomicI32::new(-1) }; SLOTS];
+/// Number of signal handlers that are (possibly) using the pipe in `PIPES`,
+/// the pipe can't be closed until this is zero.
+static WRITING: [AtomicUsize; SLOTS] = [const { AtomicUsize::new(0) }; SLOTS];
+/// Original signal handler for each signal, called after writing to the pipe.
+/// Zero if there is no handler to chain to.
+static CHAINED: [AtomicUsize; SLOTS] = [const { AtomicUsize::new(0) }; SLOTS];
+/// Whether the original signal handler was installed using `SA_SIGINFO`.
+static CHAINED_SIGINFO: [AtomicBool; SLOTS] = [const { AtomicBool::new(false) }; SLOTS];
+
+/// Signaler backed by a self-pipe.
+///
+/// # Implementation notes
+///
+/// This installs a signal handler using `sigaction(2)` with `SA_SIGINFO`. The
+/// signal handler writes a `Record`, containing the signal number and the
+/// sender, to a non-blocking pipe, the read side of which is registered with
+/// `Poll`. Unlike the `signalfd` implementation this doesn't rely on the
+/// signals being blocked in all threads, so it works no matter how many
+/// threads exist.
+///
+/// If a signal handler was already installed the signal handler calls it after
+/// writing to the pipe, and it's restored when `Signals` is dropped. Only the
+/// handler function itself is called, the `sa_mask` and `sa_flags` of the
+/// original action are not applied.
+///
+/// If the pipe is full the signal is dropped, this is fine for the signals that
+/// are merged anyway, but means real-time signals can get lost.
+///
+/// Only a single `Signals` instance can handle a signal at a time, as the
+/// signal handler only has room for a single pipe per signal.
+///
+/// When dropped the pipe is removed from `PIPES`, after which we wait for all
+/// signal handlers that loaded the pipe before that to finish writing (see
+/// `WRITING`). Only then is it safe to close the pipe, otherwise a signal
+/// handler running on another thread could write to a reused file descriptor.
+pub struct Signals {
+    /// Read end of the pipe.
+    read: RawFd,
+    /// Write end of the pipe, used in the signal handler.
+    write: RawFd,
+    /// Signals for which we installed a signal handler and their original
+    /// action, restored when dropped.
+    installed: Vec<(libc::c_int, libc::sigaction)>,
+    /// Buffer used in `receive_many`.
+    buf: Vec<MaybeUninit<Record>>,
+}
+
+/// Information written to the pipe by the signal handler.
+///
+/// NOTE: this must be smaller than `PIPE_BUF` so that it's written atomically.
+#[repr(C)]
+#[derive(Copy, Clone)]
+#[cfg_attr(
+    not(any(target_os = "linux", target_os = "android")),
+    allow(dead_code) // Only the signal is used, see `signal_info`.
+)]
+struct Record {
+    signal: libc::c_int,
+    /// `si_code`.
+    code: libc::c_int,
+    /// Only valid if send by a process, see `SignalOrigin::is_process`.
+    pid: libc::pid_t,
+    uid: libc::uid_t,
+    /// Only valid for `SignalOrigin::Queue` and `SignalOrigin::Timer`.
+    value: usize,
+}
+
+impl Record {
+    /// Create a new `Record` from `info`, may only use async-signal-safe
+    /// operations.
+    fn new(raw_signal: libc::c_int, info: *const libc::siginfo_t) -> Record {
+        let mut record = Record {
+            signal: raw_signal,
+            code: 0,
+            pid: 0,
+            uid: 0,
+            value: 0,
+        };
+        if let Some(info) = unsafe { info.as_ref() } {
+            record.code = info.si_code;
+            record.pid = unsafe { info.si_pid() };
+            record.uid = unsafe { info.si_uid() };
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            {
+                record.value = unsafe { info.si_value() }.sival_ptr as usize;
+            }
+        }
+        record
+    }
+}
+
+impl Signals {
+    pub fn new(signals: SignalSet) -> io::Result<Signals> {
+        let [read, write] = new_pipe()?;
+        let mut s = Signals {
+            read,
+            write,
+            installed: Vec::new(),
+            buf: Vec::new(),
+        };
+        for signal in signals {
+            let raw_signal = raw_signal(signal);
+            if !is_valid_signal(raw_signal) || raw_signal > MAX_RAW_SIGNAL {
+                return Err(io::Error::from_raw_os_error(libc::EINVAL));
+            }
+            let original = install_handler(raw_signal, s.write)?;
+            s.installed.push((raw_signal, original));
+        }
+        Ok(s)
+    }
+
+    pub fn receive(&mut self) -> io::Result<Option<Signal>> {
+        self.receive_info()
+            .map(|info| info.map(|info| info.signal()))
+    }
+
+    pub fn receive_info(&mut self) -> io::Result<Option<SignalInfo>> {
+        let mut info = [SignalInfo::default()];
+        self.receive_many(&mut info)
+            .map(|n| (n == 1).then_some(info[0]))
+    }
+
+    pub fn receive_many(&mut self, infos: &mut [SignalInfo]) -> io::Result<usize> {
+        if infos.is_empty() {
+            return Ok(0);
+        }
+        self.buf.resize(infos.len(), MaybeUninit::uninit());
+
+        loop {
+            let n = unsafe {
+                libc::read(
+                    self.read,
+                    self.buf.as_mut_ptr().cast(),
+                    infos.len() * size_of::<Record>(),
+                )
+            };
+            match n {
+                -1 => match io::Error::last_os_error() {
+                    ref err if err.kind() == io::ErrorKind::WouldBlock => return Ok(0),
+                    ref err if err.kind() == io::ErrorKind::Interrupted => continue,
+                    err => return Err(err),
+                },
+                n => {
+                    // NOTE: all records are written atomically, so we only
+                    // read complete records.
+                    let n = n as usize / size_of::<Record>();
+                    for (info, record) in infos.iter_mut().zip(&self.buf[..n]) {
+                        // This is safe because `read` initialised it for us.
+                        *info = signal_info(unsafe { record.assume_init_ref() });
+                    }
+                    return Ok(n);
+                }
+            }
+        }
+    }
+
+    /// The sender of a signal can only be determined on Android and Linux,
+    /// see `signal_info`.
+    pub const fn sender_info(&self) -> bool {
+        cfg!(any(target_os = "linux", target_os = "android"))
+    }
+
+    /// Receive all signals, calling `count` for each one.
+    pub fn drain<F>(&mut self, mut count: F) -> io::Result<()>
+    where
+        F: FnMut(SignalInfo, u32),
+    {
+        let mut infos = [SignalInfo::default(); 32];
+        loop {
+            let n = self.receive_many(&mut infos)?;
+            for info in &infos[..n] {
+                count(*info, 1);
+            }
+            if n < infos.len() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Create a `SignalInfo` from `record`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn signal_info(record: &Record) -> SignalInfo {
+    use crate::{SignalOrigin, SignalValue};
+
+    let signal = from_raw_signal(record.signal);
+    let mut signal_info = SignalInfo::new(signal);
+    signal_info.origin = super::signalfd::signal_origin(signal, record.code);
+    if signal_info.origin.is_process() {
+        signal_info.pid = Some(record.pid as u32);
+        signal_info.uid = Some(record.uid);
+    }
+    if let SignalOrigin::Queue | SignalOrigin::Timer = signal_info.origin {
+        signal_info.value = Some(SignalValue(record.value));
+    }
+    signal_info
+}
+
+/// Create a `SignalInfo` from `record`.
+///
+/// NOTE: the `si_code` values aren't defined for these platforms (in the libc
+/// crate), which means we can't determine if the process id and user id are
+/// valid, so we leave them unset.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn signal_info(record: &Record) -> SignalInfo {
+    SignalInfo::new(from_raw_signal(record.signal))
+}
+
+/// Create a non-blocking pipe, returning the read and write end.
+#[cfg(not(any(target_os = "ios", target_os = "macos")))]
+fn new_pipe() -> io::Result<[RawFd; 2]> {
+    let mut fds = [-1; 2];
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_NONBLOCK | libc::O_CLOEXEC) } == -1 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(fds)
+    }
+}
+
+/// Create a non-blocking pipe, returning the read and write end.
+#[cfg(any(target_os = "ios", target_os = "macos"))]
+fn new_pipe() -> io::Result<[RawFd; 2]> {
+    let mut fds = [-1; 2];
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    for fd in fds {
+        if unsafe { libc::fcntl(fd, libc::F_SETFL, libc::O_NONBLOCK) } == -1
+            || unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) } == -1
+        {
+            let err = io::Error::last_os_error();
+            let _ = unsafe { libc::close(fds[0]) };
+            let _ = unsafe { libc::close(fds[1]) };
+            return Err(err);
+        }
+    }
+    Ok(fds)
+}
+
+/// Install `handle_signal` as handler for `raw_signal`, writing to `write`.
+/// Returns the original action.
+fn install_handler(raw_signal: libc::c_int, write: RawFd) -> io::Result<libc::sigaction> {
+    let slot = raw_signal as usize;
+    if PIPES[slot]
+        .compare_exchange(-1, write, Ordering::AcqRel, Ordering::Acquire)
+        .is_err()
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "signal is already handled by another `Signals` instance",
+        ));
+    }
+
+    match set_action(raw_signal) {
+        Ok(original) => Ok(original),
+        Err(err) => {
+            CHAINED[slot].store(0, Ordering::Release);
+            PIPES[slot].store(-1, Ordering::Release);
+            Err(err)
+        }
+    }
+}
+
+/// Set the action of `raw_signal` to `handle_signal`, storing the original
+/// signal handler (if any) in `CHAINED`. Returns the original action.
+fn set_action(raw_signal: libc::c_int) -> io::Result<libc::sigaction> {
+    let slot = raw_signal as usize;
+
+    // NOTE: we store the signal handler to chain to before installing our
+    // own, so that we don't miss a call.
+    let mut original: MaybeUninit<libc::sigaction> = MaybeUninit::uninit();
+    if unsafe { libc::sigaction(raw_signal, ptr::null(), original.as_mut_ptr()) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    // This is safe because `sigaction` initialised it for us.
+    let original = unsafe { original.assume_init() };
+    match original.sa_sigaction {
+        // Calling the default action would likely terminate the process.
+        libc::SIG_DFL | libc::SIG_IGN | libc::SIG_ERR => {}
+        handler => {
+            let siginfo = original.sa_flags & libc::SA_SIGINFO != 0;
+            CHAINED_SIGINFO[slot].store(siginfo, Ordering::Release);
+            CHAINED[slot].store(handler, Ordering::Release);
+        }
+    }
+
+    let mut action: libc::sigaction = unsafe { mem::zeroed() };
+    action.sa_sigaction = handle_signal as HandlerInfo as libc::sighandler_t;
+    action.sa_flags = libc::SA_SIGINFO | libc::SA_RESTART;
+    // Block all other signals while handling one.
+    if unsafe { libc::sigfillset(&mut action.sa_mask) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    let mut original: MaybeUninit<libc::sigaction> = MaybeUninit::uninit();
+    if unsafe { libc::sigaction(raw_signal, &action, original.as_mut_ptr()) } == -1 {
+        Err(io::Error::last_os_error())
+    } else {
+        // This is safe because `sigaction` initialised it for us.
+        Ok(unsafe { original.assume_init() })
+    }
+}
+
+/// Signal handler installed without `SA_SIGINFO`.
+type Handler = extern "C" fn(libc::c_int);
+/// Signal handler installed with `SA_SIGINFO`.
+type HandlerInfo = extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut libc::c_void);
+
+/// Signal handler, writes a `Record` to the pipe and calls the original signal
+/// handler (if any).
+///
+/// # Safety
+///
+/// This must be async-signal-safe, it may only use atomics, `write(2)` and
+/// call the original signal handler.
+extern "C" fn handle_signal(
+    raw_signal: libc::c_int,
+    info: *mut libc::siginfo_t,
+    context: *mut libc::c_void,
+) {
+    let slot = raw_signal as usize;
+    // NOTE: `WRITING` must be incremented before loading the pipe, see the
+    // `Drop` implementation of `Signals`.
+    let _ = WRITING[slot].fetch_add(1, Ordering::SeqCst);
+    let fd = PIPES[slot].load(Ordering::SeqCst);
+    if fd != -1 {
+        // Don't overwrite the errno of the code we interrupted.
+        let errno = unsafe { *errno_location() };
+        let record = Record::new(raw_signal, info);
+        // NOTE: if the pipe is full the signal is dropped, see `Signals`.
+        let _ = unsafe { libc::write(fd, ptr::from_ref(&record).cast(), size_of::<Record>()) };
+        unsafe { *errno_location() = errno };
+    }
+    let _ = WRITING[slot].fetch_sub(1, Ordering::SeqCst);
+
+    match CHAINED[slot].load(Ordering::Acquire) {
+        0 => {}
+        handler if CHAINED_SIGINFO[slot].load(Ordering::Acquire) => {
+            let handler = unsafe { mem::transmute::<libc::sighandler_t, HandlerInfo>(handler) };
+            handler(raw_signal, info, context);
+        }
+        handler => {
+            let handler = unsafe { mem::transmute::<libc::sighandler_t, Handler>(handler) };
+            handler(raw_signal);
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "dragonfly"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    unsafe { libc::__errno_location() }
+}
+
+#[cfg(any(target_os = "android", target_os = "netbsd", target_os = "openbsd"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    unsafe { libc::__errno() }
+}
+
+#[cfg(any(target_os = "freebsd", target_os = "ios", target_os = "macos"))]
+unsafe fn errno_location() -> *mut libc::c_int {
+    unsafe { libc::__error() }
+}
+
+impl event::Source for Signals {
+    fn register(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        SourceFd(&self.read).register(registry, token, interests)
+    }
+
+    fn reregister(
+        &mut self,
+        registry: &Registry,
+        token: Token,
+        interests: Interest,
+    ) -> io::Result<()> {
+        SourceFd(&self.read).reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        SourceFd(&self.read).deregister(registry)
+    }
+}
+
+impl fmt::Debug for Signals {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Signals")
+            .field("read", &self.read)
+            .field("write", &self.write)
+            .finish()
+    }
+}
+
+impl Drop for Signals {
+    fn drop(&mut self) {
+        for (raw_signal, original) in &self.installed {
+            let slot = *raw_signal as usize;
+            // Stop the signal handler from using the pipe before restoring the
+            // original action and closing it. We keep chaining to the original
+            // signal handler until it's restored.
+            PIPES[slot].store(-1, Ordering::SeqCst);
+            if unsafe { libc::sigaction(*raw_signal, original, ptr::null_mut()) } == -1 {
+                let err = io::Error::last_os_error();
+                error!("error restoring signal action: {}", err);
+            }
+            CHAINED[slot].store(0, Ordering::Release);
+        }
+
+        // Signal handlers that loaded the pipe before we removed it above could
+        // still be writing to it. Those that start after can't load it anymore
+        // as `WRITING` is incremented before loading the pipe, so once this is
+        // zero it's safe to close the pipe.
+        for (raw_signal, _) in &self.installed {
+            while WRITING[*raw_signal as usize].load(Ordering::SeqCst) != 0 {
+                thread::yield_now();
+            }
+        }
+
+        for fd in [self.read, self.write] {
+            if unsafe { libc::close(fd) } == -1 {
+                let err = io::Error::last_os_error();
+                error!("error closing Signals: {}", err);
+            }
+        }
+    }
+}
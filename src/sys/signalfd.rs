@@ -1,12 +1,14 @@
 use std::mem::{MaybeUninit, size_of};
 use std::os::unix::io::RawFd;
+use std::os::unix::process::ExitStatusExt;
+use std::process::ExitStatus;
 use std::{fmt, io, ptr};
 
 use log::error;
 use mio::unix::SourceFd;
 use mio::{Interest, Registry, Token, event};
 
-use crate::{Signal, SignalSet};
+use crate::{ChildExit, Signal, SignalInfo, SignalOrigin, SignalSet, SignalValue};
 
 use super::{from_raw_signal, raw_signal};
 
@@ -29,17 +31,36 @@ pub struct Signals {
     fd: RawFd,
     /// All signals this is listening for, used in resetting the signal handlers.
     signals: libc::sigset_t,
+    /// Buffer used in `receive_many`.
+    buf: Vec<MaybeUninit<libc::signalfd_siginfo>>,
 }
 
 impl Signals {
     pub fn new(signals: SignalSet) -> io::Result<Signals> {
         create_sigset(signals)
             .and_then(|set| new_signalfd(&set).map(|fd| (fd, set)))
-            .map(|(fd, set)| (Signals { fd, signals: set }, set))
+            .map(|(fd, set)| {
+                let signals = Signals {
+                    fd,
+                    signals: set,
+                    buf: Vec::new(),
+                };
+                (signals, set)
+            })
             .and_then(|(fd, set)| block_signals(&set).map(|()| fd))
     }
 
     pub fn receive(&mut self) -> io::Result<Option<Signal>> {
+        self.receive_info()
+            .map(|info| info.map(|info| info.signal()))
+    }
+
+    /// `signalfd(2)` provides the sender of the signal.
+    pub const fn sender_info(&self) -> bool {
+        true
+    }
+
+    pub fn receive_info(&mut self) -> io::Result<Option<SignalInfo>> {
         let mut info: MaybeUninit<libc::signalfd_siginfo> = MaybeUninit::uninit();
 
         loop {
@@ -61,12 +82,129 @@ impl Signals {
                 INFO_SIZE => {
                     // This is safe because we just read into it.
                     let info = unsafe { info.assume_init() };
-                    return Ok(from_raw_signal(info.ssi_signo as libc::c_int));
+                    return Ok(Some(signal_info(&info)));
                 }
                 _ => unreachable!("read an incorrect amount of bytes from signalfd"),
             }
         }
     }
+
+    pub fn receive_many(&mut self, infos: &mut [SignalInfo]) -> io::Result<usize> {
+        if infos.is_empty() {
+            return Ok(0);
+        }
+        self.buf.resize(infos.len(), MaybeUninit::uninit());
+
+        loop {
+            let n = unsafe {
+                libc::read(
+                    self.fd,
+                    self.buf.as_mut_ptr().cast(),
+                    infos.len() * size_of::<libc::signalfd_siginfo>(),
+                )
+            };
+
+            match n {
+                -1 => match io::Error::last_os_error() {
+                    ref err if err.kind() == io::ErrorKind::WouldBlock => return Ok(0),
+                    ref err if err.kind() == io::ErrorKind::Interrupted => continue,
+                    err => return Err(err),
+                },
+                n => {
+                    let n = n as usize;
+                    debug_assert!(n.is_multiple_of(size_of::<libc::signalfd_siginfo>()));
+                    let n = n / size_of::<libc::signalfd_siginfo>();
+                    for (info, read) in infos.iter_mut().zip(&self.buf[..n]) {
+                        // This is safe because we just read into it.
+                        *info = signal_info(unsafe { read.assume_init_ref() });
+                    }
+                    return Ok(n);
+                }
+            }
+        }
+    }
+
+    /// Receive all signals, calling `count` for each one.
+    pub fn drain<F>(&mut self, mut count: F) -> io::Result<()>
+    where
+        F: FnMut(SignalInfo, u32),
+    {
+        let mut infos = [SignalInfo::default(); 32];
+        loop {
+            let n = self.receive_many(&mut infos)?;
+            for info in &infos[..n] {
+                count(*info, 1);
+            }
+            if n < infos.len() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Create a `SignalInfo` from `info`.
+fn signal_info(info: &libc::signalfd_siginfo) -> SignalInfo {
+    let signal = from_raw_signal(info.ssi_signo as libc::c_int);
+    let mut signal_info = SignalInfo::new(signal);
+    signal_info.origin = signal_origin(signal, info.ssi_code);
+    if let Signal::Child = signal {
+        signal_info.child = child_exit(info);
+    }
+    if signal_info.origin.is_process() || signal_info.child.is_some() {
+        signal_info.pid = Some(info.ssi_pid);
+        signal_info.uid = Some(info.ssi_uid);
+    }
+    if let SignalOrigin::Queue | SignalOrigin::Timer = signal_info.origin {
+        // NOTE: `ssi_ptr` holds the entire `union sigval`.
+        signal_info.value = Some(SignalValue(info.ssi_ptr as usize));
+    }
+    signal_info
+}
+
+/// Determine the origin of `signal` based on `si_code` (`ssi_code`).
+pub(super) fn signal_origin(signal: Signal, code: i32) -> SignalOrigin {
+    match code {
+        libc::SI_USER => SignalOrigin::Kill,
+        libc::SI_QUEUE => SignalOrigin::Queue,
+        libc::SI_TKILL => SignalOrigin::Thread,
+        libc::SI_TIMER => SignalOrigin::Timer,
+        // The terminal driver sends signals as the kernel.
+        libc::SI_KERNEL if send_by_tty(signal) => SignalOrigin::Tty,
+        // Positive values are set by the kernel, e.g. `CLD_EXITED`.
+        code if code > 0 => SignalOrigin::Kernel,
+        _ => SignalOrigin::Unknown,
+    }
+}
+
+/// Returns `true` if `signal` is generated by the terminal driver.
+fn send_by_tty(signal: Signal) -> bool {
+    match signal {
+        Signal::Interrupt | Signal::Quit | Signal::Hangup | Signal::WindowChange => true,
+        Signal::Other(raw_signal) => matches!(
+            raw_signal,
+            libc::SIGTSTP | libc::SIGTTIN | libc::SIGTTOU | libc::SIGCONT
+        ),
+        _ => false,
+    }
+}
+
+/// Create a `ChildExit` from the information in `SIGCHLD`'s `info`.
+fn child_exit(info: &libc::signalfd_siginfo) -> Option<ChildExit> {
+    // Convert the information into the status as returned by `waitpid(2)`.
+    let status = info.ssi_status;
+    let status = match info.ssi_code {
+        libc::CLD_EXITED => (status & 0xff) << 8,
+        libc::CLD_KILLED => status & 0x7f,
+        libc::CLD_DUMPED => (status & 0x7f) | 0x80,
+        libc::CLD_STOPPED | libc::CLD_TRAPPED => ((status & 0xff) << 8) | 0x7f,
+        libc::CLD_CONTINUED => 0xffff,
+        // Send by a process, not the kernel.
+        _ => return None,
+    };
+    Some(ChildExit {
+        pid: info.ssi_pid,
+        status: ExitStatus::from_raw(status),
+    })
 }
 
 /// Create a `libc::sigset_t` from `SignalSet`.
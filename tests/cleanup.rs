@@ -4,7 +4,155 @@
 //!
 //! This needs to run on its own and thus has its own file.
 
-use mio_signals::Signal;
+use std::mem::MaybeUninit;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::{process, ptr, thread};
+
+use mio_signals::{Backend, PipePolicy, Signal, SignalSet, Signals, raise, send_signal};
+
+#[test]
+fn pipe_policy() {
+    // NOTE: not using `SignalSet::all` here as that would interfere with the
+    // `cleanup` test on platforms that use kqueue.
+    let set = SignalSet::from(Signal::WindowChange);
+
+    // The Rust runtime ignores `SIGPIPE` by default.
+    assert_eq!(get_action(libc::SIGPIPE), libc::SIG_IGN);
+
+    let signals = Signals::builder(set)
+        .pipe_policy(PipePolicy::Default)
+        .build()
+        .unwrap();
+    assert_eq!(get_action(libc::SIGPIPE), libc::SIG_DFL);
+    drop(signals);
+    assert_eq!(get_action(libc::SIGPIPE), libc::SIG_IGN);
+
+    let signals = Signals::builder(set)
+        .pipe_policy(PipePolicy::Ignore)
+        .build()
+        .unwrap();
+    assert_eq!(get_action(libc::SIGPIPE), libc::SIG_IGN);
+    drop(signals);
+    assert_eq!(get_action(libc::SIGPIPE), libc::SIG_IGN);
+
+    let signals = Signals::builder(set)
+        .pipe_policy(PipePolicy::Receive)
+        .build()
+        .unwrap();
+    drop(signals);
+    assert_eq!(get_action(libc::SIGPIPE), libc::SIG_IGN);
+
+    // Combined with a backend.
+    let signals = Signals::builder(set)
+        .pipe_policy(PipePolicy::Receive)
+        .backend(Backend::SelfPipe)
+        .build()
+        .unwrap();
+    let action = get_action(libc::SIGPIPE);
+    assert!(action != libc::SIG_IGN && action != libc::SIG_DFL);
+    drop(signals);
+    assert_eq!(get_action(libc::SIGPIPE), libc::SIG_IGN);
+
+    // Same as `PipePolicy::Receive`.
+    let signals = Signals::new(Signal::Pipe.into()).unwrap();
+    drop(signals);
+    assert_eq!(get_action(libc::SIGPIPE), libc::SIG_IGN);
+
+    // Can't receive the signal when not using `PipePolicy::Receive`.
+    for policy in [PipePolicy::Default, PipePolicy::Ignore] {
+        let err = Signals::builder(Signal::Pipe.into())
+            .pipe_policy(policy)
+            .build()
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(get_action(libc::SIGPIPE), libc::SIG_IGN);
+    }
+}
+
+#[test]
+fn self_pipe_chaining() {
+    static CALLED: AtomicUsize = AtomicUsize::new(0);
+
+    extern "C" fn handler(_: libc::c_int) {
+        let _ = CALLED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    // NOTE: not using `SIGUSR{1,2}` as those are used in other tests.
+    let signal = Signal::Other(libc::SIGALRM);
+    let mut action: libc::sigaction = unsafe { std::mem::zeroed() };
+    action.sa_sigaction = handler as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    if unsafe { libc::sigaction(libc::SIGALRM, &action, ptr::null_mut()) } == -1 {
+        panic!("unexpected error: {}", std::io::Error::last_os_error());
+    }
+
+    let mut signals = Signals::builder(signal.into())
+        .backend(Backend::SelfPipe)
+        .build()
+        .unwrap();
+    assert_ne!(get_action(libc::SIGALRM), action.sa_sigaction);
+    // Only a single `Signals` instance can use the self-pipe for a signal.
+    let err = Signals::builder(signal.into())
+        .backend(Backend::SelfPipe)
+        .build()
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+
+    // `raise` calls the signal handler before returning.
+    raise(signal).unwrap();
+    assert_eq!(CALLED.load(Ordering::SeqCst), 1);
+    assert_eq!(signals.receive().unwrap(), Some(signal));
+    assert_eq!(signals.receive().unwrap(), None);
+
+    // After `Signals` is dropped.
+    drop(signals);
+    assert_eq!(get_action(libc::SIGALRM), action.sa_sigaction);
+    raise(signal).unwrap();
+    assert_eq!(CALLED.load(Ordering::SeqCst), 2);
+
+    action.sa_sigaction = libc::SIG_DFL;
+    if unsafe { libc::sigaction(libc::SIGALRM, &action, ptr::null_mut()) } == -1 {
+        panic!("unexpected error: {}", std::io::Error::last_os_error());
+    }
+}
+
+#[test]
+fn self_pipe_drop_while_handling() {
+    // NOTE: using `SIGURG` as its default action is to ignore it, so it
+    // doesn't matter if it arrives in between `Signals` instances.
+    let signal = Signal::Other(libc::SIGURG);
+    let done = Arc::new(AtomicBool::new(false));
+    let sender = {
+        let done = done.clone();
+        thread::spawn(move || {
+            while !done.load(Ordering::Relaxed) {
+                send_signal(process::id(), signal).unwrap();
+            }
+        })
+    };
+
+    // Dropping `Signals` while another thread is handling the signal must not
+    // write to a closed file descriptor.
+    for _ in 0..200 {
+        let mut signals = Signals::builder(signal.into())
+            .backend(Backend::SelfPipe)
+            .build()
+            .unwrap();
+        while signals.receive().unwrap().is_some() {}
+        drop(signals);
+    }
+
+    done.store(true, Ordering::Relaxed);
+    sender.join().unwrap();
+}
+
+fn get_action(raw_signal: libc::c_int) -> libc::sighandler_t {
+    let mut action: MaybeUninit<libc::sigaction> = MaybeUninit::uninit();
+    if unsafe { libc::sigaction(raw_signal, ptr::null(), action.as_mut_ptr()) } == -1 {
+        panic!("unexpected error: {}", std::io::Error::last_os_error());
+    }
+    unsafe { action.assume_init() }.sa_sigaction
+}
 
 #[cfg(any(
     target_os = "dragonfly",
@@ -81,7 +229,7 @@ mod signalfd {
     use std::mem::MaybeUninit;
     use std::{io, ptr};
 
-    use mio_signals::{Signal, SignalSet, Signals};
+    use mio_signals::{Backend, Signal, SignalSet, Signals};
 
     use super::raw_signal;
 
@@ -94,7 +242,10 @@ mod signalfd {
         }
 
         // After `Signals` is created.
-        let signals = Signals::new(SignalSet::all()).unwrap();
+        let signals = Signals::builder(SignalSet::all())
+            .backend(Backend::SignalFd)
+            .build()
+            .unwrap();
         let blocked_set = get_blocked_set().unwrap();
 
         for signal in SignalSet::all() {
@@ -133,6 +284,82 @@ mod signalfd {
     }
 }
 
+mod selfpipe {
+    use std::mem::MaybeUninit;
+    use std::{io, ptr};
+
+    use mio_signals::{Backend, Signal, SignalSet, Signals};
+
+    use super::raw_signal;
+
+    #[test]
+    fn cleanup() {
+        let set = Signal::User1 | Signal::User2;
+
+        // Before `Signals` is created.
+        let original_actions = get_sigactions(set).unwrap();
+        for action in &original_actions {
+            assert_eq!(*action, libc::SIG_DFL);
+        }
+
+        // After `Signals` is created.
+        let signals = Signals::builder(set)
+            .backend(Backend::SelfPipe)
+            .build()
+            .unwrap();
+        for (signal, action) in set.into_iter().zip(get_sigactions(set).unwrap()) {
+            assert!(
+                action != libc::SIG_DFL && action != libc::SIG_IGN,
+                "missing signal handler for signal {:?}",
+                signal
+            );
+        }
+        let blocked_set = get_blocked_set().unwrap();
+        for signal in set {
+            assert!(!is_in_set(&blocked_set, signal));
+        }
+
+        // After `Signals` is dropped.
+        drop(signals);
+        assert_eq!(get_sigactions(set).unwrap(), original_actions);
+    }
+
+    fn get_sigactions(set: SignalSet) -> io::Result<Vec<libc::sighandler_t>> {
+        set.into_iter()
+            .map(|signal| {
+                let mut action: MaybeUninit<libc::sigaction> = MaybeUninit::uninit();
+                if unsafe { libc::sigaction(raw_signal(signal), ptr::null(), action.as_mut_ptr()) }
+                    == -1
+                {
+                    Err(io::Error::last_os_error())
+                } else {
+                    // This is safe as `sigaction` fills it for us.
+                    Ok(unsafe { action.assume_init() }.sa_sigaction)
+                }
+            })
+            .collect()
+    }
+
+    fn get_blocked_set() -> io::Result<libc::sigset_t> {
+        let mut old_set: MaybeUninit<libc::sigset_t> = MaybeUninit::uninit();
+        if unsafe { libc::sigprocmask(0, ptr::null_mut(), old_set.as_mut_ptr()) } == -1 {
+            Err(io::Error::last_os_error())
+        } else {
+            // This is safe as `sigprocmask` fills it for us.
+            Ok(unsafe { old_set.assume_init() })
+        }
+    }
+
+    fn is_in_set(set: &libc::sigset_t, signal: Signal) -> bool {
+        match unsafe { libc::sigismember(set, raw_signal(signal)) } {
+            1 => true,
+            0 => false,
+            -1 => panic!("unexpected error: {}", io::Error::last_os_error()),
+            _ => unreachable!(),
+        }
+    }
+}
+
 // Keep in sync with `mio_signals::sys::raw_signal`.
 fn raw_signal(signal: Signal) -> libc::c_int {
     match signal {
@@ -141,5 +368,11 @@ fn raw_signal(signal: Signal) -> libc::c_int {
         Signal::Terminate => libc::SIGTERM,
         Signal::User1 => libc::SIGUSR1,
         Signal::User2 => libc::SIGUSR2,
+        Signal::Hangup => libc::SIGHUP,
+        Signal::Child => libc::SIGCHLD,
+        Signal::WindowChange => libc::SIGWINCH,
+        Signal::Pipe => libc::SIGPIPE,
+        Signal::Other(raw_signal) => raw_signal,
+        _ => unreachable!("not part of `SignalSet::all`"),
     }
 }
@@ -1,55 +1,127 @@
-use std::sync::mpsc::{Receiver, channel};
+//! Tests `Signals` in a multithreaded process.
+//!
+//! # Notes
+//!
+//! This doesn't use the test harness as it runs tests in their own thread,
+//! which makes it impossible to create `Signals` before any threads exist.
+
+use std::mem::MaybeUninit;
+use std::sync::mpsc::{Receiver, Sender, channel};
+use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
-use std::{io, process, thread};
+use std::{io, process, ptr, thread};
 
 use mio::{Events, Interest, Poll, Token};
-use mio_signals::{Signal, SignalSet, Signals, send_signal};
+use mio_signals::{Signal, SignalInfo, SignalSet, Signals, send_signal};
 
 const SIGNAL: Token = Token(10);
 const TIMEOUT: Duration = Duration::from_secs(1);
+/// Only Android and Linux block the signals.
+const LINUX: bool = cfg!(any(target_os = "linux", target_os = "android"));
 
 fn main() -> io::Result<()> {
     let start = Instant::now();
-    println!("\nrunning 1 test");
+    println!("\nrunning 2 tests");
+
+    // NOTE: the order is important, the first test must run before any
+    // threads are spawned.
+    signals_before_threads()?;
+    println!("test signals_before_threads ... ok");
+    threads_before_signals()?;
+    println!("test threads_before_signals ... ok\n");
+
+    println!(
+        "test result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in {:?}\n",
+        start.elapsed()
+    );
+    Ok(())
+}
+
+fn signals_before_threads() -> io::Result<()> {
+    let mut poll = Poll::new()?;
+    let mut events = Events::with_capacity(8);
+
+    let mut signals = Signals::new(SignalSet::all())?;
+    poll.registry()
+        .register(&mut signals, SIGNAL, Interest::READABLE)?;
+    // No threads exist yet, so the signals are blocked (on Android and Linux).
+    assert_eq!(is_blocked(libc::SIGINT)?, LINUX);
+
+    let handles = spawn_threads();
 
+    // Send ourselves a signal.
+    send_signal(process::id(), Signal::Interrupt)?;
+    expect_signal(&mut poll, &mut events, &mut signals, Signal::Interrupt)?;
+    join_threads(handles);
+
+    drop(signals);
+    assert!(!is_blocked(libc::SIGINT)?);
+    Ok(())
+}
+
+fn threads_before_signals() -> io::Result<()> {
     let mut poll = Poll::new()?;
     let mut events = Events::with_capacity(8);
 
+    let handles = spawn_threads();
+
     let mut signals = Signals::new(SignalSet::all())?;
     poll.registry()
         .register(&mut signals, SIGNAL, Interest::READABLE)?;
+    // The spawned threads don't have the signals blocked, so a signal handler
+    // is used instead.
+    assert!(!is_blocked(libc::SIGINT)?);
 
-    let handles = (0..5)
+    // Send ourselves a signal, which can be handled by any thread.
+    send_signal(process::id(), Signal::Interrupt)?;
+    let info = expect_signal(&mut poll, &mut events, &mut signals, Signal::Interrupt)?;
+    if LINUX {
+        assert_eq!(info.pid(), Some(process::id()));
+    }
+    send_signal(process::id(), Signal::Terminate)?;
+    expect_signal(&mut poll, &mut events, &mut signals, Signal::Terminate)?;
+    join_threads(handles);
+
+    drop(signals);
+    assert_eq!(get_action(libc::SIGINT)?, libc::SIG_DFL);
+    Ok(())
+}
+
+fn spawn_threads() -> Vec<(Sender<()>, JoinHandle<()>)> {
+    (0..5)
         .map(|_| {
             let (sender, receiver) = channel();
             let handle = thread::spawn(move || wait_for_msg(receiver));
             (sender, handle)
         })
-        .collect::<Vec<_>>();
+        .collect()
+}
 
-    // Send ourselves a signal.
-    send_signal(process::id(), Signal::Interrupt)?;
+fn join_threads(handles: Vec<(Sender<()>, JoinHandle<()>)>) {
+    for (sender, handle) in handles {
+        sender.send(()).unwrap();
+        handle.join().unwrap();
+    }
+}
 
-    poll.poll(&mut events, Some(TIMEOUT))?;
+fn wait_for_msg(receiver: Receiver<()>) {
+    receiver.recv().unwrap();
+}
+
+fn expect_signal(
+    poll: &mut Poll,
+    events: &mut Events,
+    signals: &mut Signals,
+    expected: Signal,
+) -> io::Result<SignalInfo> {
+    poll.poll(events, Some(TIMEOUT))?;
 
     for event in events.iter() {
         match event.token() {
             SIGNAL => loop {
-                match signals.receive()? {
-                    Some(Signal::Interrupt) => {
-                        for (sender, handle) in handles {
-                            sender.send(()).unwrap();
-                            handle.join().unwrap();
-                        }
-
-                        println!("test multi_threaded ... ok\n");
-                        println!(
-                            "test result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in {:?}\n",
-                            start.elapsed()
-                        );
-                        return Ok(());
-                    }
-                    Some(signal) => println!("Unexpected signal: {:?}", signal),
+                match signals.receive_info()? {
+                    Some(info) if info.signal() == expected => return Ok(info),
+                    Some(info) => println!("Unexpected signal: {:?}", info.signal()),
                     None => break, // No more signals.
                 }
             },
@@ -57,9 +129,26 @@ fn main() -> io::Result<()> {
         }
     }
 
-    panic!("failed to get signal event");
+    panic!("failed to get signal event for {:?}", expected);
 }
 
-fn wait_for_msg(receiver: Receiver<()>) {
-    let _ = receiver.recv().unwrap();
+fn is_blocked(raw_signal: libc::c_int) -> io::Result<bool> {
+    let mut set: MaybeUninit<libc::sigset_t> = MaybeUninit::uninit();
+    match unsafe { libc::pthread_sigmask(0, ptr::null(), set.as_mut_ptr()) } {
+        0 => {}
+        errno => return Err(io::Error::from_raw_os_error(errno)),
+    }
+    // This is safe as `pthread_sigmask` fills it for us.
+    let set = unsafe { set.assume_init() };
+    Ok(unsafe { libc::sigismember(&set, raw_signal) } == 1)
+}
+
+fn get_action(raw_signal: libc::c_int) -> io::Result<libc::sighandler_t> {
+    let mut action: MaybeUninit<libc::sigaction> = MaybeUninit::uninit();
+    if unsafe { libc::sigaction(raw_signal, ptr::null(), action.as_mut_ptr()) } == -1 {
+        Err(io::Error::last_os_error())
+    } else {
+        // This is safe as `sigaction` fills it for us.
+        Ok(unsafe { action.assume_init() }.sa_sigaction)
+    }
 }
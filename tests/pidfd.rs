@@ -0,0 +1,55 @@
+#![cfg(any(target_os = "linux", target_os = "android"))]
+
+use std::io::ErrorKind;
+use std::os::unix::io::AsRawFd;
+use std::os::unix::process::ExitStatusExt;
+use std::process::{self, Command};
+use std::time::Duration;
+
+use mio::{Events, Interest, Poll, Token};
+use mio_signals::{PidFd, SendError, Signal};
+
+const PIDFD: Token = Token(0);
+
+#[test]
+fn send_signal() {
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+    let mut pidfd = PidFd::from_child(&child).unwrap();
+    poll.registry()
+        .register(&mut pidfd, PIDFD, Interest::READABLE)
+        .unwrap();
+
+    // Not readable while the process is running.
+    poll.poll(&mut events, Some(Duration::from_millis(10)))
+        .unwrap();
+    assert!(events.is_empty());
+
+    pidfd.send_signal(Signal::Terminate).unwrap();
+
+    poll.poll(&mut events, Some(Duration::from_secs(1)))
+        .unwrap();
+    let event = events.iter().next().expect("no event");
+    assert_eq!(event.token(), PIDFD);
+    assert!(event.is_readable());
+
+    let status = child.wait().unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGTERM));
+
+    // The process is gone, even if its process id is reused.
+    let err = pidfd.send_signal(Signal::Terminate).unwrap_err();
+    assert!(matches!(err, SendError::NoSuchProcess), "{:?}", err);
+}
+
+#[test]
+fn open() {
+    let pidfd = PidFd::open(process::id()).unwrap();
+    assert!(pidfd.as_raw_fd() >= 0);
+
+    for pid in [0, u32::MAX] {
+        let err = PidFd::open(pid).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+}
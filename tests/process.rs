@@ -0,0 +1,770 @@
+//! Tests that send signals to the test process itself.
+//!
+//! # Notes
+//!
+//! Signals send to the process can be delivered to any thread that doesn't
+//! block it. The threads created by the default test harness are created
+//! before `Signals` is and thus don't block any signals, so these tests use
+//! their own harness and run on the main thread, one after another.
+
+use std::io::{self, BufRead, BufReader};
+use std::os::unix::process::ExitStatusExt;
+use std::process::{self, Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use mio::{Events, Interest, Poll, Token};
+use mio_signals::{
+    Backend, ChildWatcher, Escalation, ExitedChild, PipePolicy, Reaper, SendError, SendableSignal,
+    SenderFilter, Signal, SignalForwarder, SignalInfo, SignalOrigin, SignalSet, SignalValue,
+    Signals, Target, is_pid1, raise, reap_children, send_signal, send_signal_with_value,
+    terminate_with_escalation, window_size,
+};
+
+const SIGNAL: Token = Token(10);
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+fn main() {
+    let start = Instant::now();
+    let tests: &[(&str, fn())] = &[
+        ("child_exit", child_exit),
+        ("window_change", window_change),
+        ("broken_pipe", broken_pipe),
+        ("other_signal", other_signal),
+        ("sender_info", sender_info),
+        ("queued_value", queued_value),
+        ("receive_many", receive_many),
+        ("drain", drain),
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        ("sender_filter", sender_filter),
+        ("raise_signal", raise_signal),
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        ("thread_directed_signals", thread_directed_signals),
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        ("realtime_signals_are_queued", realtime_signals_are_queued),
+        ("child_watcher", child_watcher),
+        ("child_watcher_with_signals", child_watcher_with_signals),
+        ("escalation", escalation),
+        ("escalation_timed_out", escalation_timed_out),
+        ("forward_signals", forward_signals),
+        ("forward_signals_self_pipe", forward_signals_self_pipe),
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        ("child_subreaper", child_subreaper),
+    ];
+
+    println!("\nrunning {} tests", tests.len());
+    for (name, test) in tests {
+        test();
+        println!("test {} ... ok", name);
+    }
+    println!(
+        "\ntest result: ok. {} passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in {:?}\n",
+        tests.len(),
+        start.elapsed()
+    );
+}
+
+fn child_exit() {
+    let mut signals = Signals::new(SignalSet::from(Signal::Child)).unwrap();
+
+    // NOTE: we're reaping the child ourselves below.
+    let pid = Command::new("sh")
+        .args(["-c", "exit 3"])
+        .spawn()
+        .map(|child| child.id())
+        .unwrap();
+
+    let infos = receive_infos(&mut signals, 1);
+    assert_eq!(infos[0].signal(), Signal::Child);
+    let exit = infos[0].child().expect("missing child status");
+    assert_eq!(exit.pid(), pid);
+    assert_eq!(infos[0].origin(), SignalOrigin::Kernel);
+    assert_eq!(infos[0].pid(), Some(pid));
+    assert_eq!(exit.code(), Some(3));
+    assert_eq!(exit.signal(), None);
+    assert!(!exit.core_dumped());
+
+    // The child is still a zombie process, so we should be able to reap it.
+    let exits: Vec<_> = reap_children().collect();
+    assert_eq!(exits.len(), 1);
+    assert_eq!(exits[0].pid(), pid);
+    assert_eq!(exits[0].status(), ExitStatus::from_raw(3 << 8));
+    // And no more children to reap.
+    assert!(reap_children().next().is_none());
+}
+
+fn window_change() {
+    // `SIGWINCH` is ignored by default, make sure we still receive it.
+    let mut signals = Signals::new(Signal::WindowChange.into()).unwrap();
+    send_signal(process::id(), Signal::WindowChange).unwrap();
+
+    let infos = receive_infos(&mut signals, 1);
+    assert_eq!(infos[0].signal(), Signal::WindowChange);
+    // We can only get the window size if we have a controlling terminal.
+    assert_eq!(infos[0].window_size(), window_size().ok());
+}
+
+fn broken_pipe() {
+    let mut signals = Signals::builder(SignalSet::all())
+        .pipe_policy(PipePolicy::Receive)
+        .build()
+        .unwrap();
+
+    let mut fds = [0; 2];
+    assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+    assert_eq!(unsafe { libc::close(fds[0]) }, 0);
+    let n = unsafe { libc::write(fds[1], b"hello".as_ptr().cast(), 5) };
+    assert_eq!(n, -1);
+    assert_eq!(io::Error::last_os_error().raw_os_error(), Some(libc::EPIPE));
+    assert_eq!(unsafe { libc::close(fds[1]) }, 0);
+
+    let infos = receive_infos(&mut signals, 1);
+    assert_eq!(infos[0].signal(), Signal::Pipe);
+}
+
+fn other_signal() {
+    let mut signals = Signals::new(Signal::Other(libc::SIGURG).into()).unwrap();
+    send_signal(process::id(), Signal::Other(libc::SIGURG)).unwrap();
+
+    let infos = receive_infos(&mut signals, 1);
+    assert_eq!(infos[0].signal(), Signal::Other(libc::SIGURG));
+}
+
+fn sender_info() {
+    let mut signals = Signals::new(Signal::User1.into()).unwrap();
+    send_signal(process::id(), Signal::User1).unwrap();
+
+    let infos = receive_infos(&mut signals, 1);
+    let info = infos[0];
+    assert_eq!(info.signal(), Signal::User1);
+    assert_eq!(info.value(), None);
+    if cfg!(any(target_os = "linux", target_os = "android")) {
+        assert_eq!(info.origin(), SignalOrigin::Kill);
+        assert_eq!(info.pid(), Some(process::id()));
+        assert_eq!(info.uid(), Some(unsafe { libc::getuid() }));
+    } else {
+        assert_eq!(info.origin(), SignalOrigin::Unknown);
+        assert_eq!(info.pid(), None);
+        assert_eq!(info.uid(), None);
+    }
+}
+
+fn queued_value() {
+    let mut set = SignalSet::from(Signal::User2);
+    let mut values = vec![(Signal::User2, SignalValue::from_int(7))];
+    // Real-time signals are not supported on all platforms.
+    if Signal::Realtime(1).number().is_some() {
+        set |= Signal::Realtime(1);
+        values.push((Signal::Realtime(1), SignalValue::from_int(-1)));
+        values.push((Signal::Realtime(1), SignalValue::from_ptr(123 as *mut _)));
+    }
+    let mut signals = Signals::new(set).unwrap();
+    let pid = process::id();
+    for &(signal, value) in &values {
+        if let Err(err) = send_signal_with_value(pid, signal, value) {
+            // Not all platforms support `sigqueue(3)` or real-time signals.
+            match err {
+                SendError::InvalidSignal => {}
+                SendError::Other(ref err) if err.kind() == io::ErrorKind::Unsupported => {}
+                err => panic!("unexpected error: {}", err),
+            }
+            return;
+        }
+    }
+
+    let infos = receive_infos(&mut signals, values.len());
+    if cfg!(not(any(target_os = "linux", target_os = "android"))) {
+        // The value isn't available on all platforms.
+        assert!(infos.iter().all(|info| info.value().is_none()));
+        return;
+    }
+
+    // Real-time signals are queued in order, but `SIGUSR2` can be received
+    // before or after them.
+    let mut infos = infos;
+    infos.sort_by_key(|info| info.signal());
+    for (info, &(signal, value)) in infos.iter().zip(&values) {
+        assert_eq!(info.signal(), signal);
+        assert_eq!(info.origin(), SignalOrigin::Queue);
+        assert_eq!(info.pid(), Some(pid));
+        assert_eq!(info.value(), Some(value));
+    }
+    assert_eq!(infos[0].value().unwrap().as_int(), 7);
+    assert_eq!(infos[1].value().unwrap().as_int(), -1);
+    assert_eq!(infos[2].value().unwrap().as_ptr() as usize, 123);
+}
+
+fn receive_many() {
+    let set = Signal::User1 | Signal::User2 | Signal::Hangup | Signal::WindowChange;
+    let mut signals = Signals::new(set).unwrap();
+
+    let mut want = vec![
+        Signal::User1,
+        Signal::User2,
+        Signal::Hangup,
+        Signal::WindowChange,
+    ];
+    for signal in &want {
+        send_signal(process::id(), *signal).unwrap();
+    }
+
+    let mut infos = [SignalInfo::default(); 3];
+    let n = signals.receive_many(&mut infos).unwrap();
+    assert_eq!(n, 3);
+    let mut received: Vec<Signal> = infos.iter().map(SignalInfo::signal).collect();
+    let n = signals.receive_many(&mut infos).unwrap();
+    assert_eq!(n, 1);
+    received.push(infos[0].signal());
+    if received[3] == Signal::WindowChange {
+        assert_eq!(infos[0].window_size(), window_size().ok());
+    }
+    assert_eq!(signals.receive_many(&mut infos).unwrap(), 0);
+    assert_eq!(signals.receive_many(&mut []).unwrap(), 0);
+
+    received.sort();
+    want.sort();
+    assert_eq!(received, want);
+}
+
+fn drain() {
+    // Real-time signals are only supported on Android and Linux.
+    let realtime = cfg!(any(target_os = "linux", target_os = "android"));
+    let mut set = SignalSet::all();
+    if realtime {
+        set |= Signal::Realtime(1);
+    }
+    let mut signals = Signals::new(set).unwrap();
+    assert!(signals.drain().unwrap().is_empty());
+
+    let pid = process::id();
+    send_signal(pid, Signal::User1).unwrap();
+    send_signal(pid, Signal::Hangup).unwrap();
+    send_signal(pid, Signal::Hangup).unwrap();
+    if realtime {
+        for _ in 0..3 {
+            send_signal(pid, Signal::Realtime(1)).unwrap();
+        }
+    }
+
+    let counts = signals.drain().unwrap();
+    let mut want = Signal::User1 | Signal::Hangup;
+    if realtime {
+        want |= Signal::Realtime(1);
+        assert_eq!(counts.count(Signal::Realtime(1)), 3);
+    }
+    assert_eq!(counts.signals(), want);
+    assert_eq!(counts.count(Signal::User1), 1);
+    // Linux merges the signals, kqueue counts them.
+    let hangups = counts.count(Signal::Hangup);
+    assert!(
+        hangups == 1 || hangups == 2,
+        "unexpected count: {}",
+        hangups
+    );
+    assert_eq!(counts.count(Signal::User2), 0);
+    assert_eq!(counts.count(Signal::Other(1000)), 0);
+    let total: u64 = counts.iter().map(|(_, n)| u64::from(n)).sum();
+    assert_eq!(counts.total(), total);
+    assert_eq!(counts.iter().count(), want.len());
+
+    assert!(signals.drain().unwrap().is_empty());
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn sender_filter() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Send `signal` to ourselves from another process.
+    fn kill(signal: &str) {
+        let status = Command::new("kill")
+            .args(["-s", signal, &process::id().to_string()])
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    let mut signals = Signals::new(Signal::User1 | Signal::User2).unwrap();
+    let rejected_pid = Arc::new(AtomicU32::new(0));
+    let pid = rejected_pid.clone();
+    signals.set_rejected_callback(move |info| {
+        assert_eq!(info.signal(), Signal::User1);
+        pid.store(info.pid().unwrap(), Ordering::Relaxed);
+    });
+
+    // Only accept signals from ourselves.
+    signals
+        .set_sender_filter(SenderFilter::Pids(Vec::new()))
+        .unwrap();
+    kill("USR1");
+    send_signal(process::id(), Signal::User2).unwrap();
+    let infos = receive_infos(&mut signals, 1);
+    assert_eq!(infos[0].signal(), Signal::User2);
+    assert_eq!(signals.receive().unwrap(), None);
+    assert_eq!(signals.rejected(), 1);
+    let pid = rejected_pid.load(Ordering::Relaxed);
+    assert_ne!(pid, 0);
+    assert_ne!(pid, process::id());
+
+    // Accept signals from our own user.
+    let uid = unsafe { libc::getuid() };
+    signals
+        .set_sender_filter(SenderFilter::Uids(vec![uid]))
+        .unwrap();
+    kill("USR1");
+    assert_eq!(signals.receive().unwrap(), Some(Signal::User1));
+    assert_eq!(signals.rejected(), 1);
+
+    // Reject everything using a predicate, also applies to `drain`.
+    signals
+        .set_sender_filter(SenderFilter::predicate(|_| false))
+        .unwrap();
+    kill("USR1");
+    assert!(signals.drain().unwrap().is_empty());
+    assert_eq!(signals.rejected(), 2);
+
+    // Removing the filter accepts all signals again.
+    assert!(signals.remove_sender_filter().is_some());
+    kill("USR1");
+    assert_eq!(signals.receive().unwrap(), Some(Signal::User1));
+    assert_eq!(signals.rejected(), 2);
+}
+
+fn raise_signal() {
+    let mut signals = Signals::new(Signal::User1.into()).unwrap();
+    raise(Signal::User1).unwrap();
+    let infos = receive_infos(&mut signals, 1);
+    assert_eq!(infos[0].signal(), Signal::User1);
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn thread_directed_signals() {
+    use std::sync::mpsc::channel;
+    use std::sync::{Arc, Mutex};
+
+    use mio_signals::{send_signal_to_join_handle, send_signal_to_thread, thread_id};
+
+    // Created before spawning the thread below, so it has the signals blocked.
+    let signals = Signals::new(Signal::User1 | Signal::User2).unwrap();
+    let signals = Arc::new(Mutex::new(signals));
+
+    let (tid_sender, tid_receiver) = channel();
+    let (wake_sender, wake_receiver) = channel::<()>();
+    let (info_sender, info_receiver) = channel();
+    let thread_signals = signals.clone();
+    let handle = thread::spawn(move || {
+        tid_sender.send(thread_id()).unwrap();
+        // Receive a signal for every message.
+        while wake_receiver.recv().is_ok() {
+            let info = thread_signals.lock().unwrap().receive_info().unwrap();
+            info_sender.send(info).unwrap();
+        }
+    });
+    let tid = tid_receiver.recv().unwrap();
+    assert_ne!(tid, thread_id());
+
+    send_signal_to_thread(tid, Signal::User1).unwrap();
+    // The signal is pending for the thread, not the process, so the main
+    // thread can't receive it.
+    assert_eq!(signals.lock().unwrap().receive().unwrap(), None);
+    wake_sender.send(()).unwrap();
+    let info = info_receiver.recv().unwrap().expect("missing signal");
+    assert_eq!(info.signal(), Signal::User1);
+    assert_eq!(info.origin(), SignalOrigin::Thread);
+    assert_eq!(info.pid(), Some(process::id()));
+
+    send_signal_to_join_handle(&handle, Signal::User2).unwrap();
+    assert_eq!(signals.lock().unwrap().receive().unwrap(), None);
+    wake_sender.send(()).unwrap();
+    let info = info_receiver.recv().unwrap().expect("missing signal");
+    assert_eq!(info.signal(), Signal::User2);
+
+    drop(wake_sender);
+    handle.join().unwrap();
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn realtime_signals_are_queued() {
+    let mut signals = Signals::new(Signal::Realtime(1) | Signal::Realtime(2)).unwrap();
+
+    let pid = process::id();
+    send_signal(pid, Signal::Realtime(1)).unwrap();
+    send_signal(pid, Signal::Realtime(2)).unwrap();
+    send_signal(pid, Signal::Realtime(1)).unwrap();
+    send_signal(pid, Signal::Realtime(1)).unwrap();
+
+    let infos = receive_infos(&mut signals, 4);
+    let mut received: Vec<Signal> = infos.iter().map(SignalInfo::signal).collect();
+    received.sort();
+    let want = [
+        Signal::Realtime(1),
+        Signal::Realtime(1),
+        Signal::Realtime(1),
+        Signal::Realtime(2),
+    ];
+    assert_eq!(received, want);
+    assert_eq!(signals.receive().unwrap(), None);
+}
+
+fn child_watcher() {
+    let mut watcher = ChildWatcher::new().unwrap();
+    assert!(watcher.is_empty());
+
+    let mut children = [
+        Command::new("sh").args(["-c", "exit 0"]).spawn().unwrap(),
+        Command::new("sh").args(["-c", "exit 3"]).spawn().unwrap(),
+        Command::new("sleep").arg("10").spawn().unwrap(),
+    ];
+    // Not watched, so it shouldn't be reaped.
+    let mut unwatched = Command::new("sh").args(["-c", "exit 4"]).spawn().unwrap();
+    for child in &children {
+        watcher.watch(child).unwrap();
+    }
+    // Watching twice does nothing.
+    watcher.watch(&children[0]).unwrap();
+    assert_eq!(watcher.len(), 3);
+
+    send_signal(children[2].id(), Signal::Terminate).unwrap();
+    let mut exits = receive_exits(&mut watcher, 3);
+    exits.sort_by_key(|exit| exit.pid());
+    children.sort_by_key(|child| child.id());
+    for (exit, child) in exits.iter().zip(&mut children) {
+        assert_eq!(exit.pid(), child.id());
+        // Already reaped.
+        assert!(child.try_wait().is_err());
+    }
+    let statuses: Vec<_> = exits
+        .iter()
+        .map(|exit| (exit.status().code(), exit.status().signal()))
+        .collect();
+    assert!(statuses.contains(&(Some(0), None)));
+    assert!(statuses.contains(&(Some(3), None)));
+    assert!(statuses.contains(&(None, Some(libc::SIGTERM))));
+    assert!(exits.iter().all(|exit| exit.usage().max_rss() > 0));
+    assert!(watcher.is_empty());
+    assert!(watcher.receive().unwrap().is_none());
+
+    assert_eq!(unwatched.wait().unwrap().code(), Some(4));
+
+    // Not (or no longer) a child process.
+    assert!(watcher.watch_pid(exits[0].pid()).is_err());
+    assert!(watcher.watch_pid(process::id()).is_err());
+    let err = watcher.watch_pid(0).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+    let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+    watcher.watch(&child).unwrap();
+    assert!(watcher.unwatch(child.id()));
+    assert!(!watcher.unwatch(child.id()));
+    assert!(watcher.is_empty());
+    // No longer watched, so we can wait on it ourselves.
+    send_signal(child.id(), Signal::Terminate).unwrap();
+    assert_eq!(child.wait().unwrap().signal(), Some(libc::SIGTERM));
+}
+
+fn child_watcher_with_signals() {
+    let mut signals = Signals::new(SignalSet::from(Signal::User1)).unwrap();
+    let mut watcher = ChildWatcher::new().unwrap();
+
+    // NOTE: the watcher reaps the child process.
+    let pid = Command::new("sh")
+        .args(["-c", "exit 5"])
+        .spawn()
+        .map(|child| child.id())
+        .unwrap();
+    watcher.watch_pid(pid).unwrap();
+    send_signal(process::id(), Signal::User1).unwrap();
+
+    let exits = receive_exits(&mut watcher, 1);
+    assert_eq!(exits[0].pid(), pid);
+    assert_eq!(exits[0].status().code(), Some(5));
+    let infos = receive_infos(&mut signals, 1);
+    assert_eq!(infos[0].signal(), Signal::User1);
+}
+
+fn escalation() {
+    const STEPS: [SendableSignal; 2] = [
+        SendableSignal::Signal(Signal::Terminate),
+        SendableSignal::Kill,
+    ];
+    let timeout = Duration::from_millis(100);
+
+    // NOTE: the child processes are reaped by `terminate_with_escalation`.
+    let pid = Command::new("sleep")
+        .arg("10")
+        .spawn()
+        .map(|child| child.id())
+        .unwrap();
+    let termination = terminate_with_escalation(pid, &STEPS, timeout).unwrap();
+    assert_eq!(termination.pid(), pid);
+    assert_eq!(termination.status().signal(), Some(libc::SIGTERM));
+    assert_eq!(termination.step(), 0);
+    assert_eq!(termination.signal(), STEPS[0]);
+
+    // Ignores `SIGTERM`, so it must be killed.
+    let mut child = Command::new("sh")
+        .args(["-c", "trap '' TERM; echo ready; exec sleep 10"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Wait until the shell ignores `SIGTERM`.
+    let mut ready = String::new();
+    let _ = BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut ready)
+        .unwrap();
+    assert_eq!(ready, "ready\n");
+    let pid = child.id();
+    drop(child);
+    let start = Instant::now();
+    let termination = terminate_with_escalation(pid, &STEPS, timeout).unwrap();
+    assert!(start.elapsed() >= timeout);
+    assert_eq!(termination.status().signal(), Some(libc::SIGKILL));
+    assert_eq!(termination.step(), 1);
+    assert_eq!(termination.signal(), SendableSignal::Kill);
+
+    let err = terminate_with_escalation(pid, &STEPS, timeout).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ECHILD));
+    let err = terminate_with_escalation(process::id(), &[], timeout).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+}
+
+fn escalation_timed_out() {
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+
+    // `SIGURG` is ignored by default.
+    let steps = [Signal::Other(libc::SIGURG).into(); 2];
+    let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+    let mut escalation = Escalation::new(child.id(), &steps, Duration::from_millis(10)).unwrap();
+    poll.registry()
+        .register(&mut escalation, SIGNAL, Interest::READABLE)
+        .unwrap();
+
+    let err = loop {
+        match escalation.poll() {
+            Ok(None) => poll.poll(&mut events, Some(escalation.timeout())).unwrap(),
+            Ok(Some(termination)) => panic!("unexpected termination: {:?}", termination),
+            Err(err) => break err,
+        }
+    };
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    assert_eq!(escalation.step(), 1);
+    assert!(escalation.deadline() <= Instant::now());
+
+    send_signal(child.id(), SendableSignal::Kill).unwrap();
+    assert_eq!(child.wait().unwrap().signal(), Some(libc::SIGKILL));
+}
+
+fn forward_signals() {
+    let set = SignalForwarder::default_signals();
+    assert!(set.is_superset(SignalSet::all()));
+    assert!(!set.contains(Signal::Child));
+    drop(Signals::new(set).unwrap());
+
+    let signals = Signals::new(Signal::Terminate | Signal::User1).unwrap();
+    let mut child = Command::new("sh")
+        .args([
+            "-c",
+            "trap 'exit 9' USR1; echo ready; while :; do sleep 0.01; done",
+        ])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Wait until the shell handles `SIGUSR1`.
+    let mut ready = String::new();
+    let _ = BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut ready)
+        .unwrap();
+    assert_eq!(ready, "ready\n");
+    let pid = child.id();
+    drop(child);
+
+    let rewrites = [(Signal::Terminate, Signal::Quit.into())];
+    let mut forwarder = SignalForwarder::new(signals, Target::Process(pid), &rewrites);
+    assert_eq!(forwarder.forward().unwrap(), 0);
+    send_signal(process::id(), Signal::User1).unwrap();
+    let status = forwarder.run(pid).unwrap();
+    assert_eq!(status.code(), Some(9));
+    drop(forwarder);
+
+    // `SIGTERM` is rewritten into `SIGQUIT`.
+    let pid = Command::new("sleep")
+        .arg("10")
+        .spawn()
+        .map(|child| child.id())
+        .unwrap();
+    let mut forwarder = SignalForwarder::new(
+        Signals::new(Signal::Terminate.into()).unwrap(),
+        Target::Process(pid),
+        &rewrites,
+    );
+    send_signal(process::id(), Signal::Terminate).unwrap();
+    let status = forwarder.run(pid).unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGQUIT));
+}
+
+fn forward_signals_self_pipe() {
+    let signals = Signals::builder(Signal::Terminate | Signal::User1)
+        .backend(Backend::SelfPipe)
+        .build()
+        .unwrap();
+    let mut watcher = ChildWatcher::new().unwrap();
+    let other = Command::new("sh")
+        .args(["-c", "exit 2"])
+        .spawn()
+        .map(|child| child.id())
+        .unwrap();
+    watcher.watch_pid(other).unwrap();
+    let pid = Command::new("sleep")
+        .arg("10")
+        .spawn()
+        .map(|child| child.id())
+        .unwrap();
+
+    let mut forwarder = SignalForwarder::new(signals, Target::Process(pid), &[]);
+    send_signal(process::id(), Signal::User1).unwrap();
+    let status = forwarder.run_with_watcher(&mut watcher, pid).unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGUSR1));
+    drop(forwarder);
+
+    // The other child process is left to the watcher.
+    let exit = loop {
+        if let Some(exit) = watcher.receive().unwrap() {
+            break exit;
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+    assert_eq!(exit.pid(), other);
+    assert_eq!(exit.status().code(), Some(2));
+    assert!(watcher.is_empty());
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn child_subreaper() {
+    use mio_signals::{is_child_subreaper, set_child_subreaper};
+
+    assert!(!is_pid1());
+    let mut reaper = Reaper::new().unwrap();
+    set_child_subreaper(true).unwrap();
+    assert!(is_child_subreaper().unwrap());
+
+    // Owned child processes are not reaped, but don't stop other terminated
+    // child processes from being reaped.
+    let mut owned = Command::new("sh").args(["-c", "exit 3"]).spawn().unwrap();
+    reaper.own(&owned);
+    let not_owned = Command::new("sh")
+        .args(["-c", "exit 4"])
+        .spawn()
+        .map(|child| child.id())
+        .unwrap();
+    thread::sleep(Duration::from_millis(50));
+    let exit = reaper
+        .receive()
+        .unwrap()
+        .expect("zombie process not reaped");
+    assert_eq!(exit.pid(), not_owned);
+    assert_eq!(exit.code(), Some(4));
+    assert!(reaper.receive().unwrap().is_none());
+    assert_eq!(owned.wait().unwrap().code(), Some(3));
+
+    // The grandchild process is orphaned and reparented to us.
+    let mut child = Command::new("sh")
+        .args(["-c", "sh -c 'sleep 0.05; exit 5' & echo $!"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    reaper.own(&child);
+    let mut pid = String::new();
+    let _ = BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut pid)
+        .unwrap();
+    let pid: u32 = pid.trim().parse().unwrap();
+    assert_eq!(child.wait().unwrap().code(), Some(0));
+
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+    poll.registry()
+        .register(&mut reaper, SIGNAL, Interest::READABLE)
+        .unwrap();
+    let exit = loop {
+        if let Some(exit) = reaper.receive().unwrap() {
+            break exit;
+        }
+        poll.poll(&mut events, Some(TIMEOUT)).unwrap();
+        assert!(!events.is_empty(), "orphaned process not reaped");
+    };
+    assert_eq!(exit.pid(), pid);
+    assert_eq!(exit.code(), Some(5));
+    assert!(reaper.receive().unwrap().is_none());
+
+    // Reaped by their owner, so no longer owned.
+    assert!(!reaper.disown(owned.id()));
+    assert!(!reaper.disown(child.id()));
+
+    set_child_subreaper(false).unwrap();
+    assert!(!is_child_subreaper().unwrap());
+}
+
+/// Receive `n` exited child processes from `watcher`, or panic after
+/// `TIMEOUT`.
+fn receive_exits(watcher: &mut ChildWatcher, n: usize) -> Vec<ExitedChild> {
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+    poll.registry()
+        .register(watcher, SIGNAL, Interest::READABLE)
+        .unwrap();
+
+    let deadline = Instant::now() + TIMEOUT;
+    let mut exits = Vec::with_capacity(n);
+    // NOTE: the child processes could have terminated before `watcher` was
+    // registered.
+    while let Some(exit) = watcher.receive().unwrap() {
+        exits.push(exit);
+    }
+    while exits.len() < n {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        if timeout.is_zero() {
+            panic!(
+                "only received {} of {} child processes: {:?}",
+                exits.len(),
+                n,
+                exits
+            );
+        }
+        poll.poll(&mut events, Some(timeout)).unwrap();
+        while let Some(exit) = watcher.receive().unwrap() {
+            exits.push(exit);
+        }
+    }
+    poll.registry().deregister(watcher).unwrap();
+    exits
+}
+
+/// Receive `n` signals from `signals`, or panic after `TIMEOUT`.
+fn receive_infos(signals: &mut Signals, n: usize) -> Vec<SignalInfo> {
+    let mut poll = Poll::new().unwrap();
+    let mut events = Events::with_capacity(8);
+    poll.registry()
+        .register(signals, SIGNAL, Interest::READABLE)
+        .unwrap();
+
+    let deadline = Instant::now() + TIMEOUT;
+    let mut infos = Vec::with_capacity(n);
+    while infos.len() < n {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        if timeout.is_zero() {
+            panic!(
+                "only received {} of {} signals: {:?}",
+                infos.len(),
+                n,
+                infos
+            );
+        }
+        poll.poll(&mut events, Some(timeout)).unwrap();
+        while let Some(info) = signals.receive_info().unwrap() {
+            infos.push(info);
+        }
+    }
+    poll.registry().deregister(signals).unwrap();
+    infos
+}
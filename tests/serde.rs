@@ -0,0 +1,189 @@
+use mio_signals::{SendableSignal, Signal, SignalSet};
+
+#[test]
+fn serialize_signal() {
+    let tests = [
+        (Signal::Interrupt, r#""SIGINT""#),
+        (Signal::Quit, r#""SIGQUIT""#),
+        (Signal::Terminate, r#""SIGTERM""#),
+        (Signal::User1, r#""SIGUSR1""#),
+        (Signal::User2, r#""SIGUSR2""#),
+        (Signal::Hangup, r#""SIGHUP""#),
+        (Signal::Child, r#""SIGCHLD""#),
+        (Signal::WindowChange, r#""SIGWINCH""#),
+        (Signal::Pipe, r#""SIGPIPE""#),
+        (Signal::Realtime(0), r#""SIGRTMIN""#),
+        (Signal::Realtime(2), r#""SIGRTMIN+2""#),
+        (Signal::Other(libc::SIGURG), r#""SIGURG""#),
+    ];
+    for (signal, want) in tests {
+        assert_eq!(serde_json::to_string(&signal).unwrap(), want);
+        assert_eq!(serde_json::from_str::<Signal>(want).unwrap(), signal);
+    }
+}
+
+#[test]
+fn signal_round_trip() {
+    let signals = [
+        Signal::Interrupt,
+        Signal::Quit,
+        Signal::Terminate,
+        Signal::User1,
+        Signal::User2,
+        Signal::Hangup,
+        Signal::Child,
+        Signal::WindowChange,
+        Signal::Pipe,
+    ]
+    .into_iter()
+    .chain((0..=Signal::MAX_REALTIME).map(Signal::Realtime))
+    .chain(Signal::supported());
+    for signal in signals {
+        let json = serde_json::to_string(&signal).unwrap();
+        assert_eq!(serde_json::from_str::<Signal>(&json).unwrap(), signal);
+    }
+}
+
+#[test]
+fn serialize_unnamed_signal() {
+    // Signals without a name would be serialised using a platform specific
+    // number, or not round-trip.
+    let mut signals = vec![
+        Signal::Other(libc::SIGINT),
+        Signal::Other(0),
+        Signal::Other(1000),
+        Signal::Realtime(Signal::MAX_REALTIME + 1),
+    ];
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    signals.push(Signal::Other(libc::SIGRTMIN() + 6));
+    for signal in signals {
+        let err = serde_json::to_string(&signal).unwrap_err();
+        assert!(err.to_string().contains("has no portable name"), "{}", err);
+        assert!(serde_json::to_string(&SendableSignal::from(signal)).is_err());
+    }
+}
+
+#[test]
+fn deserialize_signal() {
+    let tests = [
+        (r#""SIGTERM""#, Signal::Terminate),
+        (r#""TERM""#, Signal::Terminate),
+        (r#""term""#, Signal::Terminate),
+        (r#""Terminate""#, Signal::Terminate),
+        (r#""SIGCLD""#, Signal::Child),
+    ];
+    for (input, want) in tests {
+        assert_eq!(serde_json::from_str::<Signal>(input).unwrap(), want);
+    }
+
+    let invalid = [r#""SIGFOO""#, r#""""#, "15", "null", r#"["SIGTERM"]"#];
+    for input in invalid {
+        assert!(serde_json::from_str::<Signal>(input).is_err(), "{}", input);
+    }
+}
+
+#[test]
+fn serialize_sendable_signal() {
+    let tests = [
+        (SendableSignal::Kill, r#""SIGKILL""#),
+        (SendableSignal::Stop, r#""SIGSTOP""#),
+        (SendableSignal::Continue, r#""SIGCONT""#),
+        (Signal::Terminate.into(), r#""SIGTERM""#),
+    ];
+    for (signal, want) in tests {
+        assert_eq!(serde_json::to_string(&signal).unwrap(), want);
+        assert_eq!(
+            serde_json::from_str::<SendableSignal>(want).unwrap(),
+            signal
+        );
+    }
+    assert!(serde_json::from_str::<Signal>(r#""SIGKILL""#).is_err());
+}
+
+#[test]
+fn sendable_signal_round_trip() {
+    let signals = [
+        SendableSignal::Kill,
+        SendableSignal::Stop,
+        SendableSignal::Continue,
+    ]
+    .into_iter()
+    .chain(Signal::supported().map(SendableSignal::from));
+    for signal in signals {
+        let json = serde_json::to_string(&signal).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SendableSignal>(&json).unwrap(),
+            signal
+        );
+    }
+}
+
+#[test]
+fn serialize_signal_set() {
+    let tests = [
+        (SignalSet::empty(), "[]"),
+        (Signal::Interrupt.into(), r#"["SIGINT"]"#),
+        (
+            SignalSet::all(),
+            r#"["SIGINT","SIGQUIT","SIGTERM","SIGUSR1","SIGUSR2","SIGHUP"]"#,
+        ),
+        (
+            Signal::Child | Signal::Realtime(1) | Signal::Other(libc::SIGURG),
+            r#"["SIGCHLD","SIGRTMIN+1","SIGURG"]"#,
+        ),
+    ];
+    for (set, want) in tests {
+        assert_eq!(serde_json::to_string(&set).unwrap(), want);
+        assert_eq!(serde_json::from_str::<SignalSet>(want).unwrap(), set);
+    }
+}
+
+#[test]
+fn signal_set_round_trip() {
+    let mut all = SignalSet::all() | Signal::Child | Signal::WindowChange | Signal::Pipe;
+    all.extend((0..=Signal::MAX_REALTIME).map(Signal::Realtime));
+    // NOTE: some platforms support more signals than fit in a set.
+    all.extend(Signal::supported().filter(|s| s.number().unwrap() <= Signal::MAX_OTHER));
+
+    let sets = [
+        SignalSet::empty(),
+        SignalSet::all(),
+        Signal::Child | Signal::WindowChange | Signal::Pipe,
+        Signal::Realtime(0) | Signal::Realtime(Signal::MAX_REALTIME),
+        all,
+    ];
+    for set in sets {
+        let json = serde_json::to_string(&set).unwrap();
+        assert_eq!(serde_json::from_str::<SignalSet>(&json).unwrap(), set);
+    }
+}
+
+#[test]
+fn deserialize_signal_set() {
+    let tests = [
+        (
+            r#"["int", "TERM", "SIGHUP"]"#,
+            Signal::Interrupt | Signal::Terminate | Signal::Hangup,
+        ),
+        // Duplicates are allowed.
+        (r#"["SIGINT", "SIGINT"]"#, Signal::Interrupt.into()),
+    ];
+    for (input, want) in tests {
+        assert_eq!(serde_json::from_str::<SignalSet>(input).unwrap(), want);
+    }
+
+    let invalid = [
+        r#""SIGINT""#,
+        r#"["SIGFOO"]"#,
+        r#"["Realtime(32)"]"#,
+        "[15]",
+        "null",
+    ];
+    for input in invalid {
+        assert!(
+            serde_json::from_str::<SignalSet>(input).is_err(),
+            "{}",
+            input
+        );
+    }
+}
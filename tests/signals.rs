@@ -1,16 +1,25 @@
-use std::io::Read;
+use std::io::{ErrorKind, Read};
 use std::ops::{Deref, DerefMut};
+use std::os::unix::process::{CommandExt, ExitStatusExt};
 use std::process::{Child, Command, Stdio};
 use std::thread::sleep;
 use std::time::Duration;
 
-use mio_signals::{Signal, SignalSet, Signals, send_signal};
+use mio_signals::{
+    SendError, SendableSignal, Signal, SignalSet, SignalValue, Signals, Target, can_signal,
+    process_exists, raise, send_signal, send_signal_to,
+};
 
 #[test]
 fn signal_bit_or() {
     // `Signal` and `Signal` (and `Signal`).
     assert_eq!(
-        Signal::Terminate | Signal::Quit | Signal::Interrupt | Signal::User1 | Signal::User2,
+        Signal::Terminate
+            | Signal::Quit
+            | Signal::Interrupt
+            | Signal::User1
+            | Signal::User2
+            | Signal::Hangup,
         SignalSet::all()
     );
     // `Signal` and `SignalSet`.
@@ -40,18 +49,39 @@ fn signal_bit_or() {
 
 #[test]
 fn signal_set() {
+    let other_fmt = format!("Other({})|Other({})", libc::SIGURG, libc::SIGXCPU);
     let tests = vec![
         (
             SignalSet::all(),
-            5,
+            6,
             vec![
                 Signal::Interrupt,
                 Signal::Terminate,
                 Signal::Quit,
                 Signal::User1,
                 Signal::User2,
+                Signal::Hangup,
             ],
-            "Interrupt|Quit|Terminate|User1|User2",
+            "Interrupt|Quit|Terminate|User1|User2|Hangup",
+        ),
+        (Signal::Hangup.into(), 1, vec![Signal::Hangup], "Hangup"),
+        (
+            Signal::Realtime(0) | Signal::Realtime(Signal::MAX_REALTIME),
+            2,
+            vec![Signal::Realtime(0), Signal::Realtime(Signal::MAX_REALTIME)],
+            "Realtime(0)|Realtime(31)",
+        ),
+        (
+            Signal::Other(libc::SIGURG) | Signal::Other(libc::SIGXCPU),
+            2,
+            vec![Signal::Other(libc::SIGURG), Signal::Other(libc::SIGXCPU)],
+            &*other_fmt,
+        ),
+        (
+            Signal::Interrupt | Signal::Realtime(2),
+            2,
+            vec![Signal::Interrupt, Signal::Realtime(2)],
+            "Interrupt|Realtime(2)",
         ),
         (
             Signal::Interrupt.into(),
@@ -103,7 +133,7 @@ fn signal_set() {
         assert_eq!(set.len(), size);
 
         // Test `contains`.
-        let mut contains_iter = (&expected).iter().cloned();
+        let mut contains_iter = expected.iter().cloned();
         while let Some(signal) = contains_iter.next() {
             assert!(set.contains(signal));
             assert!(set.contains::<SignalSet>(signal.into()));
@@ -111,7 +141,7 @@ fn signal_set() {
             // Set of the remaining signals.
             let mut contains_set: SignalSet = signal.into();
             for signal in contains_iter.clone() {
-                contains_set = contains_set | signal;
+                contains_set |= signal;
             }
             assert!(set.contains(contains_set));
         }
@@ -133,11 +163,130 @@ fn signal_set() {
     }
 }
 
+#[test]
+fn signal_set_empty() {
+    let set = SignalSet::empty();
+    assert!(set.is_empty());
+    assert_eq!(set.len(), 0);
+    assert_eq!(set.into_iter().next(), None);
+    assert_eq!(format!("{:?}", set), "(empty)");
+    assert!(set.contains(SignalSet::empty()));
+    assert!(!set.contains(Signal::Interrupt));
+    assert!(SignalSet::all().contains(set));
+    assert!(!SignalSet::all().is_empty());
+}
+
+#[test]
+fn signal_set_insert_remove() {
+    let mut set = SignalSet::empty();
+    assert!(set.insert(Signal::Interrupt));
+    assert!(!set.insert(Signal::Interrupt));
+    assert!(set.insert(Signal::Realtime(1)));
+    assert_eq!(set, Signal::Interrupt | Signal::Realtime(1));
+
+    assert!(set.remove(Signal::Interrupt));
+    assert!(!set.remove(Signal::Interrupt));
+    assert!(!set.remove(Signal::Quit));
+    assert_eq!(set, Signal::Realtime(1).into());
+    assert!(set.remove(Signal::Realtime(1)));
+    assert!(set.is_empty());
+}
+
+#[test]
+fn signal_set_operations() {
+    let a = Signal::Interrupt | Signal::Quit | Signal::Child;
+    let b = Signal::Quit | Signal::Terminate;
+
+    assert_eq!(a.union(b), a | b);
+    assert_eq!(
+        a | b,
+        Signal::Interrupt | Signal::Quit | Signal::Terminate | Signal::Child
+    );
+    assert_eq!(a.intersection(b), a & b);
+    assert_eq!(a & b, Signal::Quit.into());
+    assert_eq!(a & Signal::Child, Signal::Child.into());
+    assert_eq!(a.difference(b), a - b);
+    assert_eq!(a - b, Signal::Interrupt | Signal::Child);
+    assert_eq!(a - Signal::Child, Signal::Interrupt | Signal::Quit);
+    assert_eq!(a.symmetric_difference(b), a ^ b);
+    assert_eq!(a ^ b, Signal::Interrupt | Signal::Terminate | Signal::Child);
+    assert_eq!(a ^ Signal::Quit, Signal::Interrupt | Signal::Child);
+
+    assert_eq!(a & SignalSet::empty(), SignalSet::empty());
+    assert_eq!(a | SignalSet::empty(), a);
+    assert_eq!(a - a, SignalSet::empty());
+    assert_eq!(a ^ a, SignalSet::empty());
+
+    let mut set = a;
+    set |= Signal::Terminate;
+    set &= SignalSet::all();
+    assert_eq!(set, Signal::Interrupt | Signal::Quit | Signal::Terminate);
+    set -= Signal::Quit;
+    set ^= Signal::Quit | Signal::Interrupt;
+    assert_eq!(set, Signal::Quit | Signal::Terminate);
+
+    assert_eq!(
+        a.complement(),
+        Signal::Terminate | Signal::User1 | Signal::User2 | Signal::Hangup
+    );
+    assert_eq!(SignalSet::empty().complement(), SignalSet::all());
+    assert_eq!(SignalSet::all().complement(), SignalSet::empty());
+
+    assert!((a & b).is_subset(a));
+    assert!((a & b).is_subset(b));
+    assert!(a.is_subset(a));
+    assert!(!a.is_subset(b));
+    assert!(SignalSet::empty().is_subset(a));
+    assert!(a.is_superset(a & b));
+    assert!(!b.is_superset(a));
+    assert!(a.is_disjoint(Signal::Terminate.into()));
+    assert!(!a.is_disjoint(b));
+    assert!(SignalSet::empty().is_disjoint(SignalSet::empty()));
+}
+
+#[test]
+fn signal_set_from_iter() {
+    let set: SignalSet = [Signal::Interrupt, Signal::Quit, Signal::Interrupt]
+        .into_iter()
+        .collect();
+    assert_eq!(set, Signal::Interrupt | Signal::Quit);
+    assert_eq!(set.into_iter().collect::<SignalSet>(), set);
+    assert_eq!(
+        std::iter::empty().collect::<SignalSet>(),
+        SignalSet::empty()
+    );
+
+    let mut set = SignalSet::empty();
+    set.extend([Signal::Child, Signal::Realtime(3)]);
+    set.extend(SignalSet::all());
+    assert_eq!(set, SignalSet::all() | Signal::Child | Signal::Realtime(3));
+}
+
+#[test]
+fn signal_set_const() {
+    const SET: SignalSet = SignalSet::all()
+        .difference(SignalSet::empty())
+        .union(SignalSet::all());
+    const COMPLEMENT_LEN: usize = SET.complement().len();
+    assert_eq!(SET, SignalSet::all());
+    assert_eq!(COMPLEMENT_LEN, 0);
+}
+
+#[test]
+fn signals_empty_set() {
+    let err = Signals::new(SignalSet::empty()).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
 #[test]
 fn signal_set_iter_length() {
-    let set = Signal::Interrupt | Signal::Terminate | Signal::Quit | Signal::User1 | Signal::User2;
+    let set = SignalSet::all();
     let mut iter = set.into_iter();
 
+    assert!(iter.next().is_some());
+    assert_eq!(iter.len(), 5);
+    assert_eq!(iter.size_hint(), (5, Some(5)));
+
     assert!(iter.next().is_some());
     assert_eq!(iter.len(), 4);
     assert_eq!(iter.size_hint(), (4, Some(4)));
@@ -161,6 +310,470 @@ fn signal_set_iter_length() {
     assert!(iter.next().is_none());
 }
 
+#[test]
+#[should_panic = "real-time signal offset too large"]
+fn signal_set_realtime_offset_too_large() {
+    let _ = SignalSet::from(Signal::Realtime(Signal::MAX_REALTIME + 1));
+}
+
+#[test]
+fn signal_set_other() {
+    // Signals with their own variant use that.
+    assert_eq!(
+        SignalSet::from(Signal::Other(libc::SIGINT)),
+        Signal::Interrupt.into()
+    );
+    assert_eq!(
+        Signal::Other(libc::SIGTERM) | Signal::Other(libc::SIGUSR1),
+        Signal::Terminate | Signal::User1
+    );
+    assert!(SignalSet::all().contains(Signal::Other(libc::SIGHUP)));
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    assert_eq!(
+        SignalSet::from(Signal::Other(libc::SIGRTMIN() + 1)),
+        Signal::Realtime(1).into()
+    );
+
+    let set = SignalSet::from(Signal::Other(libc::SIGURG));
+    assert!(!SignalSet::all().contains(set));
+    assert_eq!(
+        set.into_iter().collect::<Vec<_>>(),
+        [Signal::Other(libc::SIGURG)]
+    );
+}
+
+#[test]
+fn signal_set_try_insert() {
+    let mut set = SignalSet::empty();
+    assert_eq!(set.try_insert(Signal::Other(libc::SIGURG)), Some(true));
+    assert_eq!(set.try_insert(Signal::Other(libc::SIGURG)), Some(false));
+    assert_eq!(set.try_insert(Signal::Realtime(0)), Some(true));
+    assert_eq!(set.try_insert(Signal::Other(0)), None);
+    assert_eq!(set.try_insert(Signal::Other(-1)), None);
+    assert_eq!(set.try_insert(Signal::Other(Signal::MAX_OTHER + 1)), None);
+    assert_eq!(
+        set.try_insert(Signal::Realtime(Signal::MAX_REALTIME + 1)),
+        None
+    );
+    assert_eq!(set, Signal::Other(libc::SIGURG) | Signal::Realtime(0));
+}
+
+#[test]
+fn send_invalid_signal() {
+    for signal in [Signal::Other(0), Signal::Other(-1), Signal::Other(1000)] {
+        let err = send_signal(std::process::id(), signal).unwrap_err();
+        assert!(matches!(err, SendError::InvalidSignal), "{:?}", err);
+        let err = raise(signal).unwrap_err();
+        assert!(matches!(err, SendError::InvalidSignal), "{:?}", err);
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn invalid_realtime_signal() {
+    // `SIGRTMIN + 31` is larger than `SIGRTMAX`.
+    let err = Signals::new(Signal::Realtime(Signal::MAX_REALTIME).into()).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+    let err = send_signal(std::process::id(), Signal::Realtime(Signal::MAX_REALTIME)).unwrap_err();
+    assert!(matches!(err, SendError::InvalidSignal), "{:?}", err);
+}
+
+#[test]
+fn signal_number() {
+    let tests = [
+        (Signal::Interrupt, libc::SIGINT),
+        (Signal::Quit, libc::SIGQUIT),
+        (Signal::Terminate, libc::SIGTERM),
+        (Signal::User1, libc::SIGUSR1),
+        (Signal::User2, libc::SIGUSR2),
+        (Signal::Hangup, libc::SIGHUP),
+        (Signal::Child, libc::SIGCHLD),
+        (Signal::WindowChange, libc::SIGWINCH),
+        (Signal::Pipe, libc::SIGPIPE),
+        (Signal::Other(libc::SIGURG), libc::SIGURG),
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        (Signal::Realtime(1), libc::SIGRTMIN() + 1),
+    ];
+    for (signal, number) in tests {
+        assert_eq!(signal.number(), Some(number), "{:?}", signal);
+        assert_eq!(Signal::from_number(number), Some(signal), "{}", number);
+    }
+
+    assert_eq!(Signal::Other(0).number(), None);
+    assert_eq!(Signal::Other(-1).number(), None);
+    assert_eq!(Signal::Other(1000).number(), None);
+    assert_eq!(Signal::from_number(0), None);
+    assert_eq!(Signal::from_number(-1), None);
+    assert_eq!(Signal::from_number(1000), None);
+
+    // Signals that can't be caught.
+    assert_eq!(Signal::Other(libc::SIGKILL).number(), None);
+    assert_eq!(Signal::Other(libc::SIGSTOP).number(), None);
+    assert_eq!(Signal::from_number(libc::SIGKILL), None);
+    assert_eq!(Signal::from_number(libc::SIGSTOP), None);
+}
+
+#[test]
+fn signal_supported() {
+    let supported: Vec<Signal> = Signal::supported().collect();
+    for signal in SignalSet::all() {
+        assert!(supported.contains(&signal), "{:?}", signal);
+    }
+    assert!(supported.contains(&Signal::Other(libc::SIGURG)));
+    assert!(!supported.contains(&Signal::Other(libc::SIGKILL)));
+    assert!(!supported.contains(&Signal::Other(libc::SIGSTOP)));
+    for signal in supported {
+        assert!(signal.number().is_some(), "{:?}", signal);
+    }
+}
+
+#[test]
+fn signal_display() {
+    let tests = [
+        (Signal::Interrupt, "SIGINT"),
+        (Signal::Quit, "SIGQUIT"),
+        (Signal::Terminate, "SIGTERM"),
+        (Signal::User1, "SIGUSR1"),
+        (Signal::User2, "SIGUSR2"),
+        (Signal::Hangup, "SIGHUP"),
+        (Signal::Child, "SIGCHLD"),
+        (Signal::WindowChange, "SIGWINCH"),
+        (Signal::Pipe, "SIGPIPE"),
+        (Signal::Realtime(0), "SIGRTMIN"),
+        (Signal::Realtime(3), "SIGRTMIN+3"),
+        (Signal::Other(libc::SIGURG), "SIGURG"),
+        (Signal::Other(libc::SIGXCPU), "SIGXCPU"),
+        (Signal::Other(1000), "Other(1000)"),
+    ];
+    for (signal, want) in tests {
+        assert_eq!(signal.to_string(), want);
+    }
+}
+
+#[test]
+fn signal_from_str() {
+    let tests = [
+        ("SIGINT", Signal::Interrupt),
+        ("INT", Signal::Interrupt),
+        ("int", Signal::Interrupt),
+        ("SigInt", Signal::Interrupt),
+        ("Interrupt", Signal::Interrupt),
+        ("SIGQUIT", Signal::Quit),
+        ("SIGTERM", Signal::Terminate),
+        ("terminate", Signal::Terminate),
+        ("SIGUSR1", Signal::User1),
+        ("SIGUSR2", Signal::User2),
+        ("SIGHUP", Signal::Hangup),
+        ("SIGCHLD", Signal::Child),
+        ("SIGCLD", Signal::Child),
+        ("SIGWINCH", Signal::WindowChange),
+        ("WindowChange", Signal::WindowChange),
+        ("SIGPIPE", Signal::Pipe),
+        ("SIGURG", Signal::Other(libc::SIGURG)),
+        ("xcpu", Signal::Other(libc::SIGXCPU)),
+        ("Realtime(2)", Signal::Realtime(2)),
+        ("SIGRTMIN", Signal::Realtime(0)),
+        ("SIGRTMIN+2", Signal::Realtime(2)),
+        (" SIGTERM ", Signal::Terminate),
+    ];
+    for (input, want) in tests {
+        assert_eq!(input.parse(), Ok(want), "{}", input);
+    }
+
+    // Numbers.
+    let number = libc::SIGTERM.to_string();
+    assert_eq!(number.parse(), Ok(Signal::Terminate));
+    let other = format!("Other({})", libc::SIGURG);
+    assert_eq!(other.parse(), Ok(Signal::Other(libc::SIGURG)));
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        let max = (libc::SIGRTMAX() - libc::SIGRTMIN()) as u8;
+        assert_eq!("SIGRTMAX".parse(), Ok(Signal::Realtime(max)));
+        assert_eq!("SIGRTMAX-1".parse(), Ok(Signal::Realtime(max - 1)));
+    }
+
+    let invalid = [
+        "",
+        "SIG",
+        "SIGFOO",
+        "0",
+        "-1",
+        "1000",
+        "Other(0)",
+        "Realtime(x)",
+        "SIGRTMIN-1",
+        "SIGRTMAX+1",
+        "SIGRTMIN+32",
+        "RTMIN+255",
+        "Realtime(32)",
+        "Other(1000)",
+        "SIGINT|SIGTERM",
+    ];
+    for input in invalid {
+        let err = input.parse::<Signal>().unwrap_err();
+        assert_eq!(err.to_string(), "invalid signal name or number");
+    }
+}
+
+#[test]
+fn signal_display_round_trip() {
+    for signal in Signal::supported() {
+        assert_eq!(signal.to_string().parse(), Ok(signal));
+        assert_eq!(format!("{:?}", signal).parse(), Ok(signal));
+    }
+}
+
+#[test]
+fn signal_display_round_trip_other() {
+    // Signals without a name and without their own variant, which shouldn't
+    // be parsed as a different variant.
+    let mut tests = vec![Signal::Other(libc::SIGINT), Signal::Other(libc::SIGURG)];
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    tests.push(Signal::Other(libc::SIGRTMIN() + 6));
+    for signal in tests {
+        assert_eq!(signal.to_string().parse(), Ok(signal));
+        assert_eq!(format!("{:?}", signal).parse(), Ok(signal));
+    }
+    assert_eq!(Signal::Other(libc::SIGINT).to_string(), "Other(2)");
+}
+
+#[test]
+fn sendable_signal_normalised() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash(signal: SendableSignal) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        signal.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let tests = [
+        (
+            SendableSignal::Signal(Signal::Other(libc::SIGCONT)),
+            SendableSignal::Continue,
+        ),
+        (
+            SendableSignal::Signal(Signal::Other(libc::SIGINT)),
+            SendableSignal::Signal(Signal::Interrupt),
+        ),
+    ];
+    for (signal, want) in tests {
+        assert_eq!(signal, want);
+        assert_eq!(hash(signal), hash(want));
+        assert_eq!(signal.cmp(&want), std::cmp::Ordering::Equal);
+    }
+
+    // Can't be sent, so not the same as `Kill`.
+    let kill = SendableSignal::Signal(Signal::Other(libc::SIGKILL));
+    assert_ne!(kill, SendableSignal::Kill);
+    let err = send_signal(std::process::id(), kill).unwrap_err();
+    assert!(matches!(err, SendError::InvalidSignal), "{:?}", err);
+}
+
+#[test]
+fn sendable_signal() {
+    let tests = [
+        (SendableSignal::Kill, libc::SIGKILL, "SIGKILL", None),
+        (SendableSignal::Stop, libc::SIGSTOP, "SIGSTOP", None),
+        (
+            SendableSignal::Continue,
+            libc::SIGCONT,
+            "SIGCONT",
+            Some(Signal::Other(libc::SIGCONT)),
+        ),
+        (
+            SendableSignal::Signal(Signal::Terminate),
+            libc::SIGTERM,
+            "SIGTERM",
+            Some(Signal::Terminate),
+        ),
+        (
+            SendableSignal::Signal(Signal::Other(libc::SIGURG)),
+            libc::SIGURG,
+            "SIGURG",
+            Some(Signal::Other(libc::SIGURG)),
+        ),
+    ];
+    for (signal, number, name, caught) in tests {
+        assert_eq!(signal.number(), Some(number), "{:?}", signal);
+        assert_eq!(SendableSignal::from_number(number), Some(signal));
+        assert_eq!(signal.to_string(), name);
+        assert_eq!(name.parse(), Ok(signal));
+        assert_eq!(format!("{:?}", signal).parse(), Ok(signal));
+        assert_eq!(number.to_string().parse(), Ok(signal));
+        assert_eq!(signal.signal(), caught);
+        if let Some(caught) = caught {
+            assert_eq!(SendableSignal::from(caught), signal);
+        }
+    }
+
+    assert_eq!("kill".parse(), Ok(SendableSignal::Kill));
+    assert_eq!("STOP".parse(), Ok(SendableSignal::Stop));
+    assert_eq!(
+        "SIGINT".parse(),
+        Ok(SendableSignal::from(Signal::Interrupt))
+    );
+    assert_eq!(SendableSignal::from_number(0), None);
+    assert!("SIGFOO".parse::<SendableSignal>().is_err());
+    assert!("0".parse::<SendableSignal>().is_err());
+}
+
+#[test]
+fn send_uncatchable_signals() {
+    let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+    send_signal(child.id(), SendableSignal::Stop).unwrap();
+    send_signal(child.id(), SendableSignal::Continue).unwrap();
+    send_signal(child.id(), SendableSignal::Kill).unwrap();
+    let status = child.wait().unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGKILL));
+}
+
+#[test]
+fn uncatchable_signal_can_not_be_registered() {
+    let err = Signals::new(Signal::Other(libc::SIGKILL).into()).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+}
+
+#[test]
+fn signal_set_from_str() {
+    let tests = [
+        ("", SignalSet::empty()),
+        ("(empty)", SignalSet::empty()),
+        ("SIGINT", Signal::Interrupt.into()),
+        ("SIGINT,SIGTERM", Signal::Interrupt | Signal::Terminate),
+        (
+            "int, term , hup",
+            Signal::Interrupt | Signal::Terminate | Signal::Hangup,
+        ),
+        ("Interrupt|Quit", Signal::Interrupt | Signal::Quit),
+        ("SIGURG", Signal::Other(libc::SIGURG).into()),
+    ];
+    for (input, want) in tests {
+        assert_eq!(input.parse(), Ok(want), "{}", input);
+    }
+
+    // Round trip using the `Debug` implementation.
+    let sets = [
+        SignalSet::empty(),
+        SignalSet::all(),
+        SignalSet::all() | Signal::Child | Signal::WindowChange | Signal::Pipe,
+        Signal::Realtime(0) | Signal::Realtime(Signal::MAX_REALTIME),
+        Signal::Other(libc::SIGURG) | Signal::Other(libc::SIGXCPU),
+    ];
+    for set in sets {
+        assert_eq!(format!("{:?}", set).parse(), Ok(set));
+    }
+
+    let invalid = [
+        "SIGINT,",
+        ",SIGINT",
+        "SIGINT,,SIGTERM",
+        "SIGFOO",
+        "Realtime(32)",
+        "1000",
+    ];
+    for input in invalid {
+        assert!(input.parse::<SignalSet>().is_err(), "{}", input);
+    }
+}
+
+#[test]
+fn signal_value() {
+    for int in [0, 1, -1, 7, i32::MIN, i32::MAX] {
+        assert_eq!(SignalValue::from_int(int).as_int(), int);
+    }
+    let mut data = 0u8;
+    let ptr: *mut u8 = &mut data;
+    assert_eq!(SignalValue::from_ptr(ptr.cast()).as_ptr(), ptr.cast());
+    assert_eq!(SignalValue::from_int(1), SignalValue::from_int(1));
+    assert_ne!(SignalValue::from_int(1), SignalValue::from_int(2));
+}
+
+#[test]
+fn send_signal_to_group() {
+    let mut child = Command::new("sh")
+        .args(["-c", "sleep 10 & sleep 10; wait"])
+        .process_group(0)
+        .spawn()
+        .unwrap();
+    // Give the shell some time to start the other processes.
+    sleep(Duration::from_millis(50));
+    send_signal_to(Target::Group(child.id()), Signal::Terminate).unwrap();
+    let status = child.wait().unwrap();
+    assert_eq!(status.signal(), Some(libc::SIGTERM));
+}
+
+#[test]
+fn send_signal_to_invalid_target() {
+    let targets = [
+        Target::Process(0),
+        Target::Process(u32::MAX),
+        Target::Group(0),
+        Target::Group(1),
+        Target::Group(u32::MAX),
+    ];
+    for target in targets {
+        let err = send_signal_to(target, Signal::User1).unwrap_err();
+        assert!(matches!(err, SendError::InvalidTarget), "{:?}", target);
+    }
+    let err = send_signal(0, Signal::User1).unwrap_err();
+    assert!(matches!(err, SendError::InvalidTarget), "{:?}", err);
+    // Can be converted into an `io::Error`.
+    let err = std::io::Error::from(err);
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn send_error() {
+    let err = std::io::Error::from_raw_os_error(libc::ESRCH);
+    assert!(matches!(SendError::from(err), SendError::NoSuchProcess));
+    let err = std::io::Error::from_raw_os_error(libc::EPERM);
+    assert!(matches!(SendError::from(err), SendError::PermissionDenied));
+    let err = std::io::Error::from_raw_os_error(libc::EINVAL);
+    assert!(matches!(SendError::from(err), SendError::InvalidSignal));
+    let err = std::io::Error::from_raw_os_error(libc::EAGAIN);
+    assert!(matches!(SendError::from(err), SendError::Other(_)));
+
+    let tests = [
+        (SendError::NoSuchProcess, libc::ESRCH),
+        (SendError::PermissionDenied, libc::EPERM),
+        (SendError::InvalidSignal, libc::EINVAL),
+    ];
+    for (err, errno) in tests {
+        let display = err.to_string();
+        let err = std::io::Error::from(err);
+        assert_eq!(err.raw_os_error(), Some(errno));
+        assert!(!display.is_empty());
+    }
+}
+
+#[test]
+fn probe_process() {
+    let pid = std::process::id();
+    assert!(process_exists(pid).unwrap());
+    assert!(can_signal(pid).unwrap());
+
+    // We're not allowed to send signals to init, unless we're root.
+    assert!(process_exists(1).unwrap());
+    assert_eq!(can_signal(1).unwrap(), unsafe { libc::geteuid() } == 0);
+
+    // Reaped child process no longer exists.
+    let mut child = Command::new("true").spawn().unwrap();
+    let child_pid = child.id();
+    assert!(child.wait().unwrap().success());
+    // NOTE: the process id could be reused, but that's very unlikely.
+    assert!(!process_exists(child_pid).unwrap());
+    assert!(!can_signal(child_pid).unwrap());
+
+    assert!(matches!(process_exists(0), Err(SendError::InvalidTarget)));
+    assert!(matches!(
+        can_signal(u32::MAX),
+        Err(SendError::InvalidTarget)
+    ));
+}
+
 #[test]
 fn receive_no_signal() {
     let mut signals = Signals::new(SignalSet::all()).expect("unable to create Signals");
@@ -174,8 +787,9 @@ fn example() {
     // Give the process some time to startup.
     sleep(Duration::from_millis(200));
 
-    let pid = child.id() as u32;
+    let pid = child.id();
 
+    send_signal(pid, Signal::Hangup).unwrap();
     send_signal(pid, Signal::User1).unwrap();
     send_signal(pid, Signal::User2).unwrap();
     send_signal(pid, Signal::Interrupt).unwrap();
@@ -188,12 +802,12 @@ fn example() {
     // In the end we do get all signals, which is what we want.
     #[cfg(any(target_os = "linux", target_os = "android"))]
     let want = format!(
-        "Call `kill -s TERM {}` to stop the process\nGot interrupt signal\nGot quit signal\nGot user signal 1\nGot user signal 2\nGot terminate signal\n",
+        "Call `kill -s TERM {}` to stop the process\nGot hangup signal\nGot interrupt signal\nGot quit signal\nGot user signal 1\nGot user signal 2\nGot terminate signal\n",
         pid
     );
     #[cfg(not(any(target_os = "linux", target_os = "android")))]
     let want = format!(
-        "Call `kill -s TERM {}` to stop the process\nGot user signal 1\nGot user signal 2\nGot interrupt signal\nGot quit signal\nGot terminate signal\n",
+        "Call `kill -s TERM {}` to stop the process\nGot hangup signal\nGot user signal 1\nGot user signal 2\nGot interrupt signal\nGot quit signal\nGot terminate signal\n",
         pid
     );
     assert_eq!(output, want);